@@ -1,10 +1,258 @@
+use crate::error::{Error, Result};
+use crate::models::{
+    check_date, check_phase_dates, Attachment, AttachmentResponse, Comment, CommentList,
+    CommentResponse, Competitor, CompetitorList, CompetitorResponse, CustomField,
+    CustomFieldDefinitionList, Endorsement, EndorsementResponse, Epic, EpicList, EpicResponse,
+    EpicUpdate, EpicWithRollup, Feature, FeatureFilters, FeatureList, FeatureResponse,
+    FeatureRollup, FeatureUpdate, Goal, GoalList, GoalResponse, Idea, IdeaFilters, IdeaList,
+    IdeaResponse, Initiative, InitiativeList, InitiativeResponse, IntegrationField,
+    IntegrationFieldList, LinkType, NewEndorsement, NewEpic, NewFeature, NewIdea, NewReleasePhase,
+    NewRequirement, NewResearchRecord, NewStrategy, NewTimeTrackingEvent, NewTodo, Paginated,
+    Persona, PersonaList, PersonaResponse, Product, ProductList, ProductResponse, ProductUserList,
+    RecordKind, RecordLink, RecordLinkList, RecordLinkResponse, Release, ReleaseList, ReleasePhase,
+    ReleasePhaseList, ReleasePhaseResponse, ReleasePhaseUpdate, ReleaseResponse, Requirement,
+    RequirementList, RequirementResponse, RequirementUpdate, ResearchRecordUpdate, StrategyUpdate,
+    TimeTrackingEvent, TimeTrackingEventList, TimeTrackingEventResponse, Todo, TodoList,
+    TodoResponse, User, UserList, UserResponse, WorkAmount, Workflow, WorkflowList, WorkflowStatus,
+};
+use crate::retry::RetryPolicy;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use surf::http::Method;
+
+/// Which page(s) of a list endpoint to request.
+///
+/// The default fetches whatever aha.io returns first. With `all` set, every
+/// page is fetched and the records are merged into a single list.
+#[derive(Clone, Debug, Default)]
+pub struct Paging {
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+    pub all: bool,
+}
+
+impl Paging {
+    pub fn all() -> Self {
+        Self {
+            all: true,
+            ..Self::default()
+        }
+    }
+}
+
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn json_body(json: &str) -> surf::Body {
+    let mut body = surf::Body::from_string(json.to_string());
+    body.set_mime(surf::http::mime::JSON);
+    body
+}
+
+fn find_by_email<'a>(users: &'a [User], email: &str) -> Option<&'a User> {
+    users.iter().find(|user| {
+        user.email
+            .as_deref()
+            .is_some_and(|e| e.eq_ignore_ascii_case(email))
+    })
+}
+
+/// Builds a `multipart/form-data` body holding one file, returning it with
+/// the content type naming its boundary.
+fn multipart_file(field: &str, file_name: &str, content: &[u8]) -> (Vec<u8>, surf::http::Mime) {
+    let boundary = format!(
+        "blimey-{:016x}{:016x}",
+        fastrand::u64(..),
+        fastrand::u64(..)
+    );
+    let file_mime = guess_mime(file_name);
+    let file_name = file_name.replace(['"', '\r', '\n'], "_");
+
+    let mut body = Vec::with_capacity(content.len() + 256);
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            boundary, field, file_name, file_mime
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    let content_type = format!("multipart/form-data; boundary={}", boundary)
+        .parse()
+        .expect("multipart content type is valid");
+    (body, content_type)
+}
+
+/// The content type of a file from its extension, for the types people
+/// usually attach.
+fn guess_mime(file_name: &str) -> String {
+    let ext = std::path::Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        _ => {
+            return surf::http::Mime::from_extension(&ext)
+                .map(|mime| mime.essence().to_string())
+                .unwrap_or_else(|| "application/octet-stream".into())
+        }
+    };
+    mime.to_string()
+}
+
+/// Comments have no comments or to-dos of their own.
+fn commentable(kind: RecordKind) -> Result<&'static str> {
+    match kind {
+        RecordKind::Comment => Err(Error::Validation(
+            "comments can't have comments or to-dos".into(),
+        )),
+        _ => Ok(kind.collection()),
+    }
+}
+
+fn time_tracked_collection(kind: RecordKind) -> Result<&'static str> {
+    match kind {
+        RecordKind::Feature | RecordKind::Requirement => Ok(kind.collection()),
+        _ => Err(Error::Validation(format!(
+            "time can't be tracked on {}s, only on features and requirements",
+            kind
+        ))),
+    }
+}
+
+fn integrated_collection(kind: RecordKind) -> Result<&'static str> {
+    match kind {
+        RecordKind::Feature | RecordKind::Requirement => Ok(kind.collection()),
+        _ => Err(Error::Validation(format!(
+            "{}s have no integration fields, only features and requirements",
+            kind
+        ))),
+    }
+}
+
+/// The type aha.io names a record by in a record link.
+fn link_record_type(kind: RecordKind) -> Result<&'static str> {
+    match kind {
+        RecordKind::Feature => Ok("Feature"),
+        RecordKind::Requirement => Ok("Requirement"),
+        RecordKind::Idea => Ok("Ideas::Idea"),
+        RecordKind::Epic => Ok("Epic"),
+        RecordKind::Release => Ok("Release"),
+        RecordKind::Comment => Err(Error::Validation("comments can't be linked".into())),
+    }
+}
+
+/// Just the tags of a feature, idea or epic response.
+#[derive(Deserialize)]
+struct TaggedRecord {
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct TagsData {
+    tags: String,
+}
+
+fn tagged_collection(kind: RecordKind) -> Result<&'static str> {
+    match kind {
+        RecordKind::Feature | RecordKind::Idea | RecordKind::Epic => Ok(kind.collection()),
+        _ => Err(Error::Validation(format!(
+            "{}s can't be tagged, only features, ideas and epics",
+            kind
+        ))),
+    }
+}
+
+fn tags_of(mut res: BTreeMap<String, TaggedRecord>, kind: RecordKind) -> Result<Vec<String>> {
+    res.remove(&kind.to_string())
+        .map(|record| record.tags)
+        .ok_or_else(|| Error::Validation(format!("aha.io returned no {}", kind)))
+}
+
+/// The part of a feature or epic update that links it to an initiative or
+/// goals. aha.io replaces the goals with the comma separated list it is sent.
+#[derive(Serialize)]
+struct StrategyLink<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initiative: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    goals: Option<String>,
+}
+
+impl<'a> StrategyLink<'a> {
+    fn initiative(initiative_id: &'a str) -> Self {
+        Self {
+            initiative: Some(initiative_id),
+            goals: None,
+        }
+    }
+
+    fn goals(current: &[Goal], goal_id: &str) -> Self {
+        let mut ids: Vec<&str> = current.iter().map(|goal| goal.id.as_str()).collect();
+        if !ids.contains(&goal_id) {
+            ids.push(goal_id);
+        }
+        Self {
+            initiative: None,
+            goals: Some(ids.join(",")),
+        }
+    }
+}
+
+fn with_query(part: &str, params: &[(&str, Option<String>)]) -> String {
+    let query: Vec<String> = params
+        .iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|v| format!("{}={}", key, encode_query_value(v)))
+        })
+        .collect();
+    if query.is_empty() {
+        part.to_string()
+    } else {
+        format!("{}?{}", part, query.join("&"))
+    }
+}
 
 #[derive(Debug)]
 pub struct AhaRequest {
     token: String,
     subdomain: String,
     base_url: String,
+    retry_policy: RetryPolicy,
 }
 
 impl AhaRequest {
@@ -13,6 +261,7 @@ impl AhaRequest {
             token: token.into(),
             subdomain: subdomain.into(),
             base_url: format!("https://{}.aha.io", subdomain),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -21,53 +270,157 @@ impl AhaRequest {
             token: token.into(),
             subdomain: subdomain.into(),
             base_url: base_url.into(),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
-    fn build_url(&self, part: &str) -> String {
-        format!("{}{}", self.base_url, part)
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn subdomain(&self) -> &str {
+        &self.subdomain
+    }
+
+    /// The url of an api path, failing rather than panicking when the
+    /// subdomain or base url doesn't make a valid url.
+    fn build_url(&self, part: &str) -> Result<surf::Url> {
+        let url = format!("{}{}", self.base_url, part);
+        surf::Url::parse(&url)
+            .map_err(|err| Error::Config(format!("{} is not a valid url: {}", url, err)))
     }
 
     fn add_headers(&self, res: surf::RequestBuilder) -> surf::RequestBuilder {
         res.header("Authorization", format!("Bearer {}", self.token))
     }
 
-    fn get(&self, part: &str) -> surf::RequestBuilder {
-        self.add_headers(surf::get(self.build_url(part)))
+    fn request(&self, method: Method, part: &str) -> Result<surf::RequestBuilder> {
+        let url = self.build_url(part)?;
+        Ok(self.add_headers(surf::RequestBuilder::new(method, url)))
+    }
+
+    fn get(&self, part: &str) -> Result<surf::RequestBuilder> {
+        self.request(Method::Get, part)
+    }
+
+    fn post(&self, part: &str) -> Result<surf::RequestBuilder> {
+        self.request(Method::Post, part)
+    }
+
+    fn put(&self, part: &str) -> Result<surf::RequestBuilder> {
+        self.request(Method::Put, part)
+    }
+
+    fn delete(&self, part: &str) -> Result<surf::RequestBuilder> {
+        self.request(Method::Delete, part)
+    }
+
+    /// Sends the request built by `build`, building it again for each retry
+    /// the retry policy allows, and returns the successful response.
+    async fn execute<F>(&self, build: F) -> Result<surf::Response>
+    where
+        F: Fn() -> Result<surf::RequestBuilder>,
+    {
+        self.execute_accepting(build, |status| status.is_success())
+            .await
+    }
+
+    /// Like `execute`, with `accept` deciding which statuses aren't errors.
+    async fn execute_accepting<F, A>(&self, build: F, accept: A) -> Result<surf::Response>
+    where
+        F: Fn() -> Result<surf::RequestBuilder>,
+        A: Fn(surf::StatusCode) -> bool,
+    {
+        let mut retry = 0;
+        let mut res = build()?.await?;
+        while self.retry_policy.should_retry(res.status(), retry) {
+            async_std::task::sleep(self.retry_policy.delay(retry, &res)).await;
+            retry += 1;
+            res = build()?.await?;
+        }
+        if !accept(res.status()) {
+            let body = res.body_string().await?;
+            return Err(Error::from_response(res.status(), &body));
+        }
+        Ok(res)
     }
 
-    fn post(&self, part: &str) -> surf::RequestBuilder {
-        self.add_headers(surf::post(self.build_url(part)))
+    async fn send<T, F>(&self, build: F) -> Result<T>
+    where
+        T: DeserializeOwned,
+        F: Fn() -> Result<surf::RequestBuilder>,
+    {
+        let body = self.execute(build).await?.body_string().await?;
+        Ok(serde_json::from_str(&body)?)
     }
 
-    fn put(&self, part: &str) -> surf::RequestBuilder {
-        self.add_headers(surf::put(self.build_url(part)))
+    /// Like `send`, for creates and updates, which aha.io may answer with a
+    /// 201 or 204 and no body; there is no record to return then.
+    async fn send_saved<T, F>(&self, build: F) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        F: Fn() -> Result<surf::RequestBuilder>,
+    {
+        let mut res = self.execute(build).await?;
+        let body = res.body_string().await?;
+        if res.status() == surf::StatusCode::NoContent || body.trim().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&body)?))
     }
 
-    pub fn list_products(&self, updated_since: &Option<String>) -> surf::RequestBuilder {
-        let mut url_str = "/api/v1/products".to_string();
-        if updated_since.is_some() {
-            let part = format!(
-                "?updated_since={}",
-                updated_since.clone().unwrap_or_default()
-            );
-            url_str.push_str(&part);
+    async fn list<T: Paginated + DeserializeOwned>(
+        &self,
+        part: &str,
+        params: &[(&str, Option<String>)],
+        paging: &Paging,
+    ) -> Result<T> {
+        if paging.page == Some(0) || paging.per_page == Some(0) {
+            return Err(Error::Validation(
+                "page and per_page must be greater than zero".into(),
+            ));
+        }
+        let page_url = |page: Option<u64>| {
+            let mut params = params.to_vec();
+            params.push(("page", page.map(|p| p.to_string())));
+            params.push(("per_page", paging.per_page.map(|p| p.to_string())));
+            with_query(part, &params)
+        };
+        let first = if paging.all { Some(1) } else { paging.page };
+        let mut list: T = self.send(|| self.get(&page_url(first))).await?;
+        if paging.all {
+            let total_pages = list.pagination().total_pages;
+            for page in 2..=total_pages {
+                let next: T = self.send(|| self.get(&page_url(Some(page)))).await?;
+                list.append(next);
+            }
         }
-        self.get(&url_str)
+        Ok(list)
+    }
+
+    pub async fn list_products(
+        &self,
+        updated_since: &Option<String>,
+        paging: &Paging,
+    ) -> Result<ProductList> {
+        let params = [("updated_since", updated_since.clone())];
+        self.list("/api/v1/products", &params, paging).await
     }
 
-    pub fn get_product(&self, product_id: &str) -> surf::RequestBuilder {
+    pub async fn get_product(&self, product_id: &str) -> Result<Product> {
         let url_str = format!("/api/v1/products/{}", product_id);
-        self.get(&url_str)
+        let res: ProductResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.product)
     }
 
-    pub fn create_product(
+    pub async fn create_product(
         &self,
         name: &str,
         prefix: &str,
         parent_id: &Option<String>,
         workspace_type: &str,
-    ) -> surf::RequestBuilder {
+    ) -> Result<Option<Product>> {
         #[derive(Deserialize, Serialize)]
         struct Product {
             product: ProductData,
@@ -90,17 +443,20 @@ impl AhaRequest {
                 workspace_type: workspace_type.into(),
             },
         };
-        self.post(url_str)
-            .body(surf::Body::from_json(data).unwrap())
+        let body = serde_json::to_string(data)?;
+        let res: Option<ProductResponse> = self
+            .send_saved(|| Ok(self.post(url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.product))
     }
 
-    pub fn update_product(
+    pub async fn update_product(
         &self,
         product_id: &str,
         name: &Option<String>,
         prefix: &Option<String>,
         parent_id: &Option<String>,
-    ) -> surf::RequestBuilder {
+    ) -> Result<Option<Product>> {
         #[derive(Deserialize, Serialize)]
         struct Product {
             product: ProductData,
@@ -115,6 +471,9 @@ impl AhaRequest {
             #[serde(skip_serializing_if = "Option::is_none")]
             parent_id: Option<String>,
         }
+        if name.is_none() && prefix.is_none() && parent_id.is_none() {
+            return Err(Error::Validation("nothing to update for product".into()));
+        }
         let url_str = format!("/api/v1/products/{}", product_id);
         let data = &Product {
             product: ProductData {
@@ -123,21 +482,182 @@ impl AhaRequest {
                 parent_id: parent_id.clone(),
             },
         };
-        self.put(&url_str)
-            .body(surf::Body::from_json(data).unwrap())
+        let body = serde_json::to_string(data)?;
+        let res: Option<ProductResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.product))
+    }
+
+    pub async fn list_users(&self, paging: &Paging) -> Result<UserList> {
+        self.list("/api/v1/users", &[], paging).await
+    }
+
+    /// Gets a user by id, or by email by searching the account's users.
+    pub async fn get_user(&self, user: &str) -> Result<User> {
+        if user.contains('@') {
+            return self.find_user_by_email(user).await;
+        }
+        let url_str = format!("/api/v1/users/{}", user);
+        let res: UserResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.user)
+    }
+
+    pub async fn list_users_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<ProductUserList> {
+        let url_str = format!("/api/v1/products/{}/users", product_id);
+        self.list(&url_str, &[], paging).await
     }
 
-    pub fn get_release(&self, release_id: &str) -> surf::RequestBuilder {
+    async fn find_user_by_email(&self, email: &str) -> Result<User> {
+        let users = self.list_users(&Paging::all()).await?;
+        find_by_email(&users.users, email)
+            .cloned()
+            .ok_or_else(|| Error::Validation(format!("no user with email {}", email)))
+    }
+
+    /// Turns emails into user ids, leaving anything else as it is. The
+    /// account's users are only fetched when there is an email to look up.
+    pub async fn resolve_user_ids(&self, users: &[String]) -> Result<Vec<String>> {
+        if !users.iter().any(|user| user.contains('@')) {
+            return Ok(users.to_vec());
+        }
+        let known = self.list_users(&Paging::all()).await?;
+        users
+            .iter()
+            .map(|user| {
+                if !user.contains('@') {
+                    return Ok(user.clone());
+                }
+                find_by_email(&known.users, user)
+                    .map(|found| found.id.clone())
+                    .ok_or_else(|| Error::Validation(format!("no user with email {}", user)))
+            })
+            .collect()
+    }
+
+    pub async fn get_release(&self, release_id: &str) -> Result<Release> {
+        let url_str = format!("/api/v1/releases/{}", release_id);
+        let res: ReleaseResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.release)
+    }
+
+    pub async fn delete_release(&self, release_id: &str) -> Result<()> {
         let url_str = format!("/api/v1/releases/{}", release_id);
-        self.get(&url_str)
+        self.execute(|| self.delete(&url_str)).await?;
+        Ok(())
     }
 
-    pub fn list_releases_for_product(&self, product_id: &str) -> surf::RequestBuilder {
+    pub async fn list_release_phases(
+        &self,
+        release_id: &str,
+        paging: &Paging,
+    ) -> Result<ReleasePhaseList> {
+        let url_str = format!("/api/v1/releases/{}/release_phases", release_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_release_phase(&self, phase_id: &str) -> Result<ReleasePhase> {
+        let url_str = format!("/api/v1/release_phases/{}", phase_id);
+        let res: ReleasePhaseResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.release_phase)
+    }
+
+    /// Gets a release together with all of its phases and milestones.
+    pub async fn get_release_with_phases(&self, release_id: &str) -> Result<Release> {
+        let mut release = self.get_release(release_id).await?;
+        release.release_phases = self
+            .list_release_phases(&release.id, &Paging::all())
+            .await?
+            .release_phases;
+        Ok(release)
+    }
+
+    pub async fn create_release_phase(
+        &self,
+        release_id: &str,
+        phase: &NewReleasePhase,
+    ) -> Result<Option<ReleasePhase>> {
+        #[derive(Serialize)]
+        struct ReleasePhase<'a> {
+            release_phase: &'a NewReleasePhase,
+        }
+
+        if phase.name.trim().is_empty() {
+            return Err(Error::Validation("a release phase needs a name".into()));
+        }
+        if phase.phase_type != "phase" && phase.phase_type != "milestone" {
+            return Err(Error::Validation(format!(
+                "unknown release phase type {}, expected phase or milestone",
+                phase.phase_type
+            )));
+        }
+        check_phase_dates(Some(&phase.start_on), phase.end_on.as_deref())?;
+        let url_str = format!("/api/v1/releases/{}/release_phases", release_id);
+        let body = serde_json::to_string(&ReleasePhase {
+            release_phase: phase,
+        })?;
+        let res: Option<ReleasePhaseResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.release_phase))
+    }
+
+    pub async fn update_release_phase(
+        &self,
+        phase_id: &str,
+        phase: &ReleasePhaseUpdate,
+    ) -> Result<Option<ReleasePhase>> {
+        #[derive(Serialize)]
+        struct ReleasePhase<'a> {
+            release_phase: &'a ReleasePhaseUpdate,
+        }
+
+        if phase.is_empty() {
+            return Err(Error::Validation(
+                "nothing to update for release phase".into(),
+            ));
+        }
+        check_phase_dates(phase.start_on.as_deref(), phase.end_on.as_deref())?;
+        let url_str = format!("/api/v1/release_phases/{}", phase_id);
+        let body = serde_json::to_string(&ReleasePhase {
+            release_phase: phase,
+        })?;
+        let res: Option<ReleasePhaseResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.release_phase))
+    }
+
+    pub async fn delete_release_phase(&self, phase_id: &str) -> Result<()> {
+        let url_str = format!("/api/v1/release_phases/{}", phase_id);
+        self.execute(|| self.delete(&url_str)).await?;
+        Ok(())
+    }
+
+    pub async fn list_workflows_for_product(&self, product_id: &str) -> Result<Vec<Workflow>> {
+        let url_str = format!("/api/v1/products/{}/workflows", product_id);
+        let res: WorkflowList = self.send(|| self.get(&url_str)).await?;
+        Ok(res.workflows)
+    }
+
+    pub async fn list_releases_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<ReleaseList> {
         let url_str = format!("/api/v1/products/{}/releases", product_id);
-        self.get(&url_str)
+        self.list(&url_str, &[], paging).await
     }
 
-    pub fn create_release_for_product(&self, product_id: &str, name: &str) -> surf::RequestBuilder {
+    pub async fn create_release_for_product(
+        &self,
+        product_id: &str,
+        name: &str,
+    ) -> Result<Option<Release>> {
         #[derive(Deserialize, Serialize)]
         struct Release {
             release: ReleaseData,
@@ -151,17 +671,20 @@ impl AhaRequest {
         let data = &Release {
             release: ReleaseData { name: name.into() },
         };
-        self.post(&url_str)
-            .body(surf::Body::from_json(data).unwrap())
+        let body = serde_json::to_string(data)?;
+        let res: Option<ReleaseResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.release))
     }
 
-    pub fn update_release_for_product(
+    pub async fn update_release_for_product(
         &self,
         product_id: &str,
         release_id: &str,
         name: &Option<String>,
         parent_id: &Option<String>,
-    ) -> surf::RequestBuilder {
+    ) -> Result<Option<Release>> {
         #[derive(Deserialize, Serialize)]
         struct Release {
             release: ReleaseData,
@@ -174,6 +697,9 @@ impl AhaRequest {
             #[serde(skip_serializing_if = "Option::is_none")]
             parent_id: Option<String>,
         }
+        if name.is_none() && parent_id.is_none() {
+            return Err(Error::Validation("nothing to update for release".into()));
+        }
         let url_str = format!("/api/v1/products/{}/releases/{}", product_id, release_id);
         let data = &Release {
             release: ReleaseData {
@@ -181,50 +707,1197 @@ impl AhaRequest {
                 parent_id: parent_id.clone(),
             },
         };
-        self.put(&url_str)
-            .body(surf::Body::from_json(data).unwrap())
+        let body = serde_json::to_string(data)?;
+        let res: Option<ReleaseResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.release))
+    }
+
+    pub async fn list_initiatives_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<InitiativeList> {
+        let url_str = format!("/api/v1/products/{}/initiatives", product_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_initiative(&self, initiative_id: &str) -> Result<Initiative> {
+        let url_str = format!("/api/v1/initiatives/{}", initiative_id);
+        let res: InitiativeResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.initiative)
+    }
+
+    pub async fn create_initiative_for_product(
+        &self,
+        product_id: &str,
+        initiative: &NewStrategy,
+    ) -> Result<Option<Initiative>> {
+        #[derive(Serialize)]
+        struct Initiative<'a> {
+            initiative: &'a NewStrategy,
+        }
+
+        if initiative.name.trim().is_empty() {
+            return Err(Error::Validation("an initiative needs a name".into()));
+        }
+        let url_str = format!("/api/v1/products/{}/initiatives", product_id);
+        let body = serde_json::to_string(&Initiative { initiative })?;
+        let res: Option<InitiativeResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.initiative))
+    }
+
+    pub async fn update_initiative(
+        &self,
+        initiative_id: &str,
+        initiative: &StrategyUpdate,
+    ) -> Result<Option<Initiative>> {
+        #[derive(Serialize)]
+        struct Initiative<'a> {
+            initiative: &'a StrategyUpdate,
+        }
+
+        if initiative.is_empty() {
+            return Err(Error::Validation("nothing to update for initiative".into()));
+        }
+        let url_str = format!("/api/v1/initiatives/{}", initiative_id);
+        let body = serde_json::to_string(&Initiative { initiative })?;
+        let res: Option<InitiativeResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.initiative))
+    }
+
+    pub async fn list_goals_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<GoalList> {
+        let url_str = format!("/api/v1/products/{}/goals", product_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_goal(&self, goal_id: &str) -> Result<Goal> {
+        let url_str = format!("/api/v1/goals/{}", goal_id);
+        let res: GoalResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.goal)
+    }
+
+    pub async fn create_goal_for_product(
+        &self,
+        product_id: &str,
+        goal: &NewStrategy,
+    ) -> Result<Option<Goal>> {
+        #[derive(Serialize)]
+        struct Goal<'a> {
+            goal: &'a NewStrategy,
+        }
+
+        if goal.name.trim().is_empty() {
+            return Err(Error::Validation("a goal needs a name".into()));
+        }
+        let url_str = format!("/api/v1/products/{}/goals", product_id);
+        let body = serde_json::to_string(&Goal { goal })?;
+        let res: Option<GoalResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.goal))
+    }
+
+    pub async fn update_goal(&self, goal_id: &str, goal: &StrategyUpdate) -> Result<Option<Goal>> {
+        #[derive(Serialize)]
+        struct Goal<'a> {
+            goal: &'a StrategyUpdate,
+        }
+
+        if goal.is_empty() {
+            return Err(Error::Validation("nothing to update for goal".into()));
+        }
+        let url_str = format!("/api/v1/goals/{}", goal_id);
+        let body = serde_json::to_string(&Goal { goal })?;
+        let res: Option<GoalResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.goal))
+    }
+
+    pub async fn link_feature_to_initiative(
+        &self,
+        feature_id: &str,
+        initiative_id: &str,
+    ) -> Result<Option<Feature>> {
+        #[derive(Serialize)]
+        struct Feature<'a> {
+            feature: StrategyLink<'a>,
+        }
+
+        let url_str = format!("/api/v1/features/{}", feature_id);
+        let body = serde_json::to_string(&Feature {
+            feature: StrategyLink::initiative(initiative_id),
+        })?;
+        let res: Option<FeatureResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.feature))
+    }
+
+    pub async fn link_epic_to_initiative(
+        &self,
+        epic_id: &str,
+        initiative_id: &str,
+    ) -> Result<Option<Epic>> {
+        #[derive(Serialize)]
+        struct Epic<'a> {
+            epic: StrategyLink<'a>,
+        }
+
+        let url_str = format!("/api/v1/epics/{}", epic_id);
+        let body = serde_json::to_string(&Epic {
+            epic: StrategyLink::initiative(initiative_id),
+        })?;
+        let res: Option<EpicResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.epic))
+    }
+
+    /// Adds a goal to a feature, keeping the goals it is already linked to.
+    pub async fn link_feature_to_goal(
+        &self,
+        feature_id: &str,
+        goal_id: &str,
+    ) -> Result<Option<Feature>> {
+        #[derive(Serialize)]
+        struct Feature<'a> {
+            feature: StrategyLink<'a>,
+        }
+
+        let feature = self.get_feature(feature_id).await?;
+        let url_str = format!("/api/v1/features/{}", feature.id);
+        let body = serde_json::to_string(&Feature {
+            feature: StrategyLink::goals(&feature.goals, goal_id),
+        })?;
+        let res: Option<FeatureResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.feature))
+    }
+
+    /// Adds a goal to an epic, keeping the goals it is already linked to.
+    pub async fn link_epic_to_goal(&self, epic_id: &str, goal_id: &str) -> Result<Option<Epic>> {
+        #[derive(Serialize)]
+        struct Epic<'a> {
+            epic: StrategyLink<'a>,
+        }
+
+        let epic = self.get_epic(epic_id).await?;
+        let url_str = format!("/api/v1/epics/{}", epic.id);
+        let body = serde_json::to_string(&Epic {
+            epic: StrategyLink::goals(&epic.goals, goal_id),
+        })?;
+        let res: Option<EpicResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.epic))
+    }
+
+    pub async fn list_personas_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<PersonaList> {
+        let url_str = format!("/api/v1/products/{}/personas", product_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_persona(&self, persona_id: &str) -> Result<Persona> {
+        let url_str = format!("/api/v1/personas/{}", persona_id);
+        let res: PersonaResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.persona)
+    }
+
+    pub async fn create_persona_for_product(
+        &self,
+        product_id: &str,
+        persona: &NewResearchRecord,
+    ) -> Result<Option<Persona>> {
+        #[derive(Serialize)]
+        struct Persona<'a> {
+            persona: &'a NewResearchRecord,
+        }
+
+        if persona.name.trim().is_empty() {
+            return Err(Error::Validation("a persona needs a name".into()));
+        }
+        let url_str = format!("/api/v1/products/{}/personas", product_id);
+        let body = serde_json::to_string(&Persona { persona })?;
+        let res: Option<PersonaResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.persona))
+    }
+
+    pub async fn update_persona(
+        &self,
+        persona_id: &str,
+        persona: &ResearchRecordUpdate,
+    ) -> Result<Option<Persona>> {
+        #[derive(Serialize)]
+        struct Persona<'a> {
+            persona: &'a ResearchRecordUpdate,
+        }
+
+        if persona.is_empty() {
+            return Err(Error::Validation("nothing to update for persona".into()));
+        }
+        let url_str = format!("/api/v1/personas/{}", persona_id);
+        let body = serde_json::to_string(&Persona { persona })?;
+        let res: Option<PersonaResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.persona))
+    }
+
+    pub async fn list_competitors_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<CompetitorList> {
+        let url_str = format!("/api/v1/products/{}/competitors", product_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_competitor(&self, competitor_id: &str) -> Result<Competitor> {
+        let url_str = format!("/api/v1/competitors/{}", competitor_id);
+        let res: CompetitorResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.competitor)
+    }
+
+    pub async fn create_competitor_for_product(
+        &self,
+        product_id: &str,
+        competitor: &NewResearchRecord,
+    ) -> Result<Option<Competitor>> {
+        #[derive(Serialize)]
+        struct Competitor<'a> {
+            competitor: &'a NewResearchRecord,
+        }
+
+        if competitor.name.trim().is_empty() {
+            return Err(Error::Validation("a competitor needs a name".into()));
+        }
+        let url_str = format!("/api/v1/products/{}/competitors", product_id);
+        let body = serde_json::to_string(&Competitor { competitor })?;
+        let res: Option<CompetitorResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.competitor))
+    }
+
+    pub async fn update_competitor(
+        &self,
+        competitor_id: &str,
+        competitor: &ResearchRecordUpdate,
+    ) -> Result<Option<Competitor>> {
+        #[derive(Serialize)]
+        struct Competitor<'a> {
+            competitor: &'a ResearchRecordUpdate,
+        }
+
+        if competitor.is_empty() {
+            return Err(Error::Validation("nothing to update for competitor".into()));
+        }
+        let url_str = format!("/api/v1/competitors/{}", competitor_id);
+        let body = serde_json::to_string(&Competitor { competitor })?;
+        let res: Option<CompetitorResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.competitor))
     }
 
-    pub fn list_features_for_product(&self, product_id: &str) -> surf::RequestBuilder {
+    pub async fn list_features_for_product(
+        &self,
+        product_id: &str,
+        filters: &FeatureFilters,
+        paging: &Paging,
+    ) -> Result<FeatureList> {
         let url_str = format!("/api/v1/products/{}/features", product_id);
-        self.get(&url_str)
+        let params = [
+            ("tag", filters.tag.clone()),
+            ("assigned_to_user", filters.assigned_to_user.clone()),
+            ("updated_since", filters.updated_since.clone()),
+        ];
+        self.list(&url_str, &params, paging).await
     }
 
-    pub fn get_feature(&self, feature_id: &str) -> surf::RequestBuilder {
+    pub async fn get_feature(&self, feature_id: &str) -> Result<Feature> {
         let url_str = format!("/api/v1/features/{}", feature_id);
-        self.get(&url_str)
+        let res: FeatureResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.feature)
+    }
+
+    pub async fn create_feature(
+        &self,
+        release_id: &str,
+        feature: &NewFeature,
+    ) -> Result<Option<Feature>> {
+        #[derive(Serialize)]
+        struct Feature<'a> {
+            feature: &'a NewFeature,
+        }
+
+        if feature.name.trim().is_empty() {
+            return Err(Error::Validation("a feature needs a name".into()));
+        }
+        let url_str = format!("/api/v1/releases/{}/features", release_id);
+        let body = serde_json::to_string(&Feature { feature })?;
+        let res: Option<FeatureResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.feature))
     }
 
-    pub fn update_feature(
+    pub async fn update_feature(
         &self,
         feature_id: &str,
-        name: &Option<String>,
-        start_date: &Option<String>,
-        due_date: &Option<String>,
-    ) -> surf::RequestBuilder {
-        #[derive(Deserialize, Serialize)]
-        struct Feature {
-            feature: FeatureData,
+        feature: &FeatureUpdate,
+    ) -> Result<Option<Feature>> {
+        #[derive(Serialize)]
+        struct Feature<'a> {
+            feature: &'a FeatureUpdate,
         }
 
-        #[derive(Deserialize, Serialize)]
-        struct FeatureData {
-            #[serde(skip_serializing_if = "Option::is_none")]
-            name: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            start_date: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            due_date: Option<String>,
+        if feature.is_empty() {
+            return Err(Error::Validation("nothing to update for feature".into()));
+        }
+        let estimates = [
+            &feature.original_estimate,
+            &feature.remaining_estimate,
+            &feature.work_done,
+        ];
+        for estimate in estimates.iter().copied().flatten() {
+            estimate.parse::<WorkAmount>()?;
         }
         let url_str = format!("/api/v1/features/{}", feature_id);
-        let data = &Feature {
-            feature: FeatureData {
-                name: name.clone(),
-                start_date: start_date.clone(),
-                due_date: due_date.clone(),
+        let body = serde_json::to_string(&Feature { feature })?;
+        let res: Option<FeatureResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.feature))
+    }
+
+    /// Resolves a status name or id against the workflow the feature is
+    /// in, which is the product workflow holding its current status.
+    pub async fn resolve_feature_status(
+        &self,
+        feature: &Feature,
+        status: &str,
+    ) -> Result<WorkflowStatus> {
+        let reference = feature.reference_num.as_deref().unwrap_or(&feature.id);
+        let (product_id, current) = match (&feature.product_id, &feature.workflow_status) {
+            (Some(product_id), Some(current)) => (product_id, current),
+            _ => {
+                return Err(Error::Validation(format!(
+                    "feature {} has no workflow to pick a status from",
+                    reference
+                )))
+            }
+        };
+        let workflows = self.list_workflows_for_product(product_id).await?;
+        let workflow = workflows
+            .iter()
+            .find(|w| w.workflow_statuses.iter().any(|s| s.id == current.id))
+            .ok_or_else(|| {
+                Error::Validation(format!("no workflow found for feature {}", reference))
+            })?;
+        workflow.find_status(status).cloned().ok_or_else(|| {
+            let names: Vec<&str> = workflow
+                .workflow_statuses
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect();
+            Error::Validation(format!(
+                "'{}' is not a status in the {} workflow, expected one of: {}",
+                status,
+                workflow.name,
+                names.join(", ")
+            ))
+        })
+    }
+
+    pub async fn delete_feature(&self, feature_id: &str) -> Result<()> {
+        let url_str = format!("/api/v1/features/{}", feature_id);
+        self.execute(|| self.delete(&url_str)).await?;
+        Ok(())
+    }
+
+    pub async fn list_epics_for_product(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<EpicList> {
+        let url_str = format!("/api/v1/products/{}/epics", product_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_epic(&self, epic_id: &str) -> Result<Epic> {
+        let url_str = format!("/api/v1/epics/{}", epic_id);
+        let res: EpicResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.epic)
+    }
+
+    pub async fn create_epic(&self, release_id: &str, epic: &NewEpic) -> Result<Option<Epic>> {
+        #[derive(Serialize)]
+        struct Epic<'a> {
+            epic: &'a NewEpic,
+        }
+
+        if epic.name.trim().is_empty() {
+            return Err(Error::Validation("an epic needs a name".into()));
+        }
+        let url_str = format!("/api/v1/releases/{}/epics", release_id);
+        let body = serde_json::to_string(&Epic { epic })?;
+        let res: Option<EpicResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.epic))
+    }
+
+    pub async fn update_epic(&self, epic_id: &str, epic: &EpicUpdate) -> Result<Option<Epic>> {
+        #[derive(Serialize)]
+        struct Epic<'a> {
+            epic: &'a EpicUpdate,
+        }
+
+        if epic.is_empty() {
+            return Err(Error::Validation("nothing to update for epic".into()));
+        }
+        let url_str = format!("/api/v1/epics/{}", epic_id);
+        let body = serde_json::to_string(&Epic { epic })?;
+        let res: Option<EpicResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.epic))
+    }
+
+    pub async fn list_features_for_epic(
+        &self,
+        epic_id: &str,
+        paging: &Paging,
+    ) -> Result<FeatureList> {
+        let url_str = format!("/api/v1/epics/{}/features", epic_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    /// Fetches an epic and adds up the progress of all of its features.
+    pub async fn get_epic_with_rollup(&self, epic_id: &str) -> Result<EpicWithRollup> {
+        let epic = self.get_epic(epic_id).await?;
+        let url_str = format!("/api/v1/epics/{}/features", epic.id);
+        // list responses only carry a few fields unless asked for more
+        let fields =
+            "id,reference_num,name,workflow_status,original_estimate,remaining_estimate,work_done";
+        let features: FeatureList = self
+            .list(&url_str, &[("fields", Some(fields.into()))], &Paging::all())
+            .await?;
+        Ok(EpicWithRollup {
+            epic,
+            rollup: FeatureRollup::from_features(&features.features),
+        })
+    }
+
+    pub async fn list_ideas_for_product(
+        &self,
+        product_id: &str,
+        filters: &IdeaFilters,
+        paging: &Paging,
+    ) -> Result<IdeaList> {
+        let url_str = format!("/api/v1/products/{}/ideas", product_id);
+        let params = [
+            ("workflow_status", filters.workflow_status.clone()),
+            ("category", filters.category.clone()),
+            ("tag", filters.tag.clone()),
+            ("updated_since", filters.updated_since.clone()),
+        ];
+        self.list(&url_str, &params, paging).await
+    }
+
+    pub async fn get_idea(&self, idea_id: &str) -> Result<Idea> {
+        let url_str = format!("/api/v1/ideas/{}", idea_id);
+        let res: IdeaResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.idea)
+    }
+
+    pub async fn create_idea(&self, product_id: &str, idea: &NewIdea) -> Result<Option<Idea>> {
+        #[derive(Serialize)]
+        struct Idea<'a> {
+            idea: &'a NewIdea,
+        }
+
+        if idea.name.trim().is_empty() {
+            return Err(Error::Validation("an idea needs a name".into()));
+        }
+        let url_str = format!("/api/v1/products/{}/ideas", product_id);
+        let body = serde_json::to_string(&Idea { idea })?;
+        let res: Option<IdeaResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.idea))
+    }
+
+    pub async fn create_idea_endorsement(
+        &self,
+        idea_id: &str,
+        endorsement: &NewEndorsement,
+    ) -> Result<Option<Endorsement>> {
+        #[derive(Serialize)]
+        struct Endorsement<'a> {
+            idea_endorsement: &'a NewEndorsement,
+        }
+
+        if endorsement.email.trim().is_empty() {
+            return Err(Error::Validation(
+                "a vote needs the email of the portal user it is cast for".into(),
+            ));
+        }
+        let url_str = format!("/api/v1/ideas/{}/endorsements", idea_id);
+        let body = serde_json::to_string(&Endorsement {
+            idea_endorsement: endorsement,
+        })?;
+        let res: Option<EndorsementResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.idea_endorsement))
+    }
+
+    pub async fn list_comments_for_idea(
+        &self,
+        idea_id: &str,
+        paging: &Paging,
+    ) -> Result<CommentList> {
+        self.list_comments(RecordKind::Idea, idea_id, paging).await
+    }
+
+    pub async fn list_comments(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        paging: &Paging,
+    ) -> Result<CommentList> {
+        let url_str = format!("/api/v1/{}/{}/comments", commentable(kind)?, record_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    /// Adds a comment to a record. aha.io expects the body as HTML.
+    pub async fn create_comment(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        body: &str,
+    ) -> Result<Option<Comment>> {
+        #[derive(Serialize)]
+        struct Comment<'a> {
+            comment: CommentData<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct CommentData<'a> {
+            body: &'a str,
+        }
+
+        if body.trim().is_empty() {
+            return Err(Error::Validation("a comment needs a body".into()));
+        }
+        let url_str = format!("/api/v1/{}/{}/comments", commentable(kind)?, record_id);
+        let body = serde_json::to_string(&Comment {
+            comment: CommentData { body },
+        })?;
+        let res: Option<CommentResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.comment))
+    }
+
+    /// The to-dos assigned to the user the token belongs to.
+    pub async fn list_my_todos(&self, paging: &Paging) -> Result<TodoList> {
+        self.list("/api/v1/me/tasks", &[], paging).await
+    }
+
+    pub async fn list_todos(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        paging: &Paging,
+    ) -> Result<TodoList> {
+        let url_str = format!("/api/v1/{}/{}/tasks", commentable(kind)?, record_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn get_todo(&self, todo_id: &str) -> Result<Todo> {
+        let url_str = format!("/api/v1/tasks/{}", todo_id);
+        let res: TodoResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.task)
+    }
+
+    pub async fn create_todo(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        todo: &NewTodo,
+    ) -> Result<Option<Todo>> {
+        #[derive(Serialize)]
+        struct Task<'a> {
+            task: &'a NewTodo,
+        }
+
+        if todo.name.trim().is_empty() {
+            return Err(Error::Validation("a to-do needs a name".into()));
+        }
+        let url_str = format!("/api/v1/{}/{}/tasks", commentable(kind)?, record_id);
+        let body = serde_json::to_string(&Task { task: todo })?;
+        let res: Option<TodoResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.task))
+    }
+
+    pub async fn complete_todo(&self, todo_id: &str) -> Result<Option<Todo>> {
+        #[derive(Serialize)]
+        struct Task {
+            task: TaskData,
+        }
+
+        #[derive(Serialize)]
+        struct TaskData {
+            status: &'static str,
+        }
+
+        let url_str = format!("/api/v1/tasks/{}", todo_id);
+        let body = serde_json::to_string(&Task {
+            task: TaskData {
+                status: "completed",
             },
+        })?;
+        let res: Option<TodoResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.task))
+    }
+
+    pub async fn get_tags(&self, kind: RecordKind, record_id: &str) -> Result<Vec<String>> {
+        let url_str = format!("/api/v1/{}/{}", tagged_collection(kind)?, record_id);
+        let res: BTreeMap<String, TaggedRecord> = self.send(|| self.get(&url_str)).await?;
+        tags_of(res, kind)
+    }
+
+    /// Replaces all tags of a feature, idea or epic.
+    pub async fn set_tags(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        tags: &[String],
+    ) -> Result<Vec<String>> {
+        let url_str = format!("/api/v1/{}/{}", tagged_collection(kind)?, record_id);
+        let mut data = BTreeMap::new();
+        data.insert(
+            kind.to_string(),
+            TagsData {
+                tags: tags.join(","),
+            },
+        );
+        let body = serde_json::to_string(&data)?;
+        let res: Option<BTreeMap<String, TaggedRecord>> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        match res {
+            Some(res) => tags_of(res, kind),
+            None => Ok(tags.to_vec()),
+        }
+    }
+
+    /// Adds tags to a record, keeping the ones it already has.
+    pub async fn add_tags(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        tags: &[String],
+    ) -> Result<Vec<String>> {
+        let mut current = self.get_tags(kind, record_id).await?;
+        for tag in tags {
+            if !current.contains(tag) {
+                current.push(tag.clone());
+            }
+        }
+        self.set_tags(kind, record_id, &current).await
+    }
+
+    /// Removes tags from a record; tags it doesn't have are ignored.
+    pub async fn remove_tags(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        tags: &[String],
+    ) -> Result<Vec<String>> {
+        let mut current = self.get_tags(kind, record_id).await?;
+        current.retain(|tag| !tags.contains(tag));
+        self.set_tags(kind, record_id, &current).await
+    }
+
+    pub async fn list_custom_field_definitions(
+        &self,
+        product_id: &str,
+        paging: &Paging,
+    ) -> Result<CustomFieldDefinitionList> {
+        let url_str = format!("/api/v1/products/{}/custom_field_definitions", product_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn list_time_tracking_events(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        paging: &Paging,
+    ) -> Result<TimeTrackingEventList> {
+        let url_str = format!(
+            "/api/v1/{}/{}/time_tracking_events",
+            time_tracked_collection(kind)?,
+            record_id
+        );
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn log_time(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        event: &NewTimeTrackingEvent,
+    ) -> Result<Option<TimeTrackingEvent>> {
+        #[derive(Serialize)]
+        struct TimeTrackingEvent<'a> {
+            time_tracking_event: &'a NewTimeTrackingEvent,
+        }
+
+        let collection = time_tracked_collection(kind)?;
+        if event.work_done <= 0.0 {
+            return Err(Error::Validation("no work to log".into()));
+        }
+        if let Some(date) = &event.work_date {
+            check_date(date)?;
+        }
+        let url_str = format!("/api/v1/{}/{}/time_tracking_events", collection, record_id);
+        let body = serde_json::to_string(&TimeTrackingEvent {
+            time_tracking_event: event,
+        })?;
+        let res: Option<TimeTrackingEventResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.time_tracking_event))
+    }
+
+    pub async fn list_record_links(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+    ) -> Result<Vec<RecordLink>> {
+        link_record_type(kind)?;
+        let url_str = format!("/api/v1/{}/{}/record_links", kind.collection(), record_id);
+        let res: RecordLinkList = self.send(|| self.get(&url_str)).await?;
+        Ok(res.record_links)
+    }
+
+    pub async fn get_record_link(&self, link_id: &str) -> Result<RecordLink> {
+        let url_str = format!("/api/v1/record_links/{}", link_id);
+        let res: RecordLinkResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.record_link)
+    }
+
+    /// Links a record to another one, e.g. PROD-12 depends on PROD-9. The
+    /// first record is the parent of the link.
+    pub async fn create_record_link(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        link_type: LinkType,
+        other_kind: RecordKind,
+        other_id: &str,
+    ) -> Result<Option<RecordLink>> {
+        #[derive(Serialize)]
+        struct RecordLink<'a> {
+            record_link: RecordLinkData<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct RecordLinkData<'a> {
+            record_type: &'a str,
+            record_id: &'a str,
+            link_type: u32,
+        }
+
+        link_record_type(kind)?;
+        let url_str = format!("/api/v1/{}/{}/record_links", kind.collection(), record_id);
+        let body = serde_json::to_string(&RecordLink {
+            record_link: RecordLinkData {
+                record_type: link_record_type(other_kind)?,
+                record_id: other_id,
+                link_type: link_type.id(),
+            },
+        })?;
+        let res: Option<RecordLinkResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.record_link))
+    }
+
+    pub async fn delete_record_link(&self, link_id: &str) -> Result<()> {
+        let url_str = format!("/api/v1/record_links/{}", link_id);
+        self.execute(|| self.delete(&url_str)).await?;
+        Ok(())
+    }
+
+    pub async fn list_integration_fields(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+    ) -> Result<Vec<IntegrationField>> {
+        let url_str = format!(
+            "/api/v1/{}/{}/integration_fields",
+            integrated_collection(kind)?,
+            record_id
+        );
+        let res: IntegrationFieldList = self.send(|| self.get(&url_str)).await?;
+        Ok(res.integration_fields)
+    }
+
+    /// Writes integration fields of a feature or requirement, e.g. the `key`
+    /// and `url` of the issue it is linked to in `integration_id`.
+    pub async fn set_integration_fields(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        integration_id: &str,
+        fields: &[(String, String)],
+    ) -> Result<Vec<IntegrationField>> {
+        #[derive(Serialize)]
+        struct Fields<'a> {
+            integration_fields: Vec<Field<'a>>,
+        }
+
+        #[derive(Serialize)]
+        struct Field<'a> {
+            name: &'a str,
+            value: &'a str,
+        }
+
+        let collection = integrated_collection(kind)?;
+        if fields.is_empty() {
+            return Err(Error::Validation("no integration fields to set".into()));
+        }
+        let url_str = format!(
+            "/api/v1/{}/{}/integrations/{}/fields",
+            collection, record_id, integration_id
+        );
+        let body = serde_json::to_string(&Fields {
+            integration_fields: fields
+                .iter()
+                .map(|(name, value)| Field { name, value })
+                .collect(),
+        })?;
+        let res: Option<IntegrationFieldList> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        match res {
+            Some(res) => Ok(res.integration_fields),
+            None => self.list_integration_fields(kind, record_id).await,
+        }
+    }
+
+    /// Finds the feature of a product linked to an issue key, e.g. a Jira
+    /// key, by looking through the integration fields of all its features.
+    pub async fn find_feature_by_integration_key(
+        &self,
+        product_id: &str,
+        key: &str,
+    ) -> Result<Feature> {
+        let url_str = format!("/api/v1/products/{}/features", product_id);
+        let params = [(
+            "fields",
+            Some("id,reference_num,name,integration_fields".into()),
+        )];
+        let list: FeatureList = self.list(&url_str, &params, &Paging::all()).await?;
+        let key = key.trim();
+        let matches: Vec<&Feature> = list
+            .features
+            .iter()
+            .filter(|feature| {
+                feature.integration_fields.iter().any(|field| {
+                    field.name == "key"
+                        && field
+                            .text()
+                            .is_some_and(|value| value.eq_ignore_ascii_case(key))
+                })
+            })
+            .collect();
+        match matches.as_slice() {
+            [feature] => self.get_feature(&feature.id).await,
+            [] => Err(Error::Validation(format!(
+                "no feature in {} is linked to {}",
+                product_id, key
+            ))),
+            features => Err(Error::Validation(format!(
+                "{} is linked to more than one feature: {}",
+                key,
+                features
+                    .iter()
+                    .map(|f| f.reference_num.as_deref().unwrap_or(&f.id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// Sets custom fields of a feature, epic, idea or release by key, leaving
+    /// its other custom fields as they are.
+    pub async fn set_custom_fields(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        values: &Map<String, Value>,
+    ) -> Result<Vec<CustomField>> {
+        #[derive(Serialize)]
+        struct CustomFieldsData<'a> {
+            custom_fields: &'a Map<String, Value>,
+        }
+
+        #[derive(Deserialize)]
+        struct CustomFieldsRecord {
+            #[serde(default)]
+            custom_fields: Vec<CustomField>,
+        }
+
+        let collection = match kind {
+            RecordKind::Feature | RecordKind::Epic | RecordKind::Idea | RecordKind::Release => {
+                kind.collection()
+            }
+            _ => {
+                return Err(Error::Validation(format!(
+                    "{}s have no custom fields blimey can set",
+                    kind
+                )))
+            }
+        };
+        if values.is_empty() {
+            return Err(Error::Validation("no custom fields to set".into()));
+        }
+        let url_str = format!("/api/v1/{}/{}", collection, record_id);
+        let mut data = BTreeMap::new();
+        data.insert(
+            kind.to_string(),
+            CustomFieldsData {
+                custom_fields: values,
+            },
+        );
+        let body = serde_json::to_string(&data)?;
+        let saved: Option<BTreeMap<String, CustomFieldsRecord>> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        let mut res = match saved {
+            Some(res) => res,
+            None => self.send(|| self.get(&url_str)).await?,
+        };
+        res.remove(&kind.to_string())
+            .map(|record| record.custom_fields)
+            .ok_or_else(|| Error::Validation(format!("aha.io returned no {}", kind)))
+    }
+
+    /// The attachments of a record, taken from the record itself.
+    pub async fn list_attachments(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+    ) -> Result<Vec<Attachment>> {
+        #[derive(Deserialize)]
+        struct AttachedRecord {
+            #[serde(default)]
+            attachments: Vec<Attachment>,
+        }
+
+        let url_str = format!("/api/v1/{}/{}", kind.collection(), record_id);
+        let mut res: BTreeMap<String, AttachedRecord> = self.send(|| self.get(&url_str)).await?;
+        res.remove(&kind.to_string())
+            .map(|record| record.attachments)
+            .ok_or_else(|| Error::Validation(format!("aha.io returned no {}", kind)))
+    }
+
+    /// Attaches a file to a record, uploading it as multipart form data.
+    pub async fn upload_attachment(
+        &self,
+        kind: RecordKind,
+        record_id: &str,
+        file_name: &str,
+        content: &[u8],
+    ) -> Result<Option<Attachment>> {
+        let url_str = format!("/api/v1/{}/{}/attachments", kind.collection(), record_id);
+        let (body, content_type) = multipart_file("attachment[data]", file_name, content);
+        let res: Option<AttachmentResponse> = self
+            .send_saved(|| {
+                let mut part = surf::Body::from_bytes(body.clone());
+                part.set_mime(content_type.clone());
+                Ok(self.post(&url_str)?.body(part))
+            })
+            .await?;
+        Ok(res.map(|res| res.attachment))
+    }
+
+    /// Whether a url points at the aha.io account itself, comparing scheme,
+    /// host and port so the token is never sent anywhere else.
+    fn is_aha_url(&self, url: &surf::Url) -> bool {
+        match surf::Url::parse(&self.base_url) {
+            Ok(base) => url.origin() == base.origin(),
+            Err(_) => false,
+        }
+    }
+
+    /// Downloads the contents of an attachment. The token is only sent to
+    /// aha.io itself, not to the storage it may redirect to.
+    pub async fn download_attachment(&self, attachment: &Attachment) -> Result<Vec<u8>> {
+        let url = attachment.download_url.as_deref().ok_or_else(|| {
+            Error::Validation(format!("attachment {} has no download url", attachment.id))
+        })?;
+        let url = if url.starts_with('/') {
+            self.build_url(url)?
+        } else {
+            surf::Url::parse(url).map_err(|err| {
+                Error::Validation(format!(
+                    "attachment {} has an invalid download url {}: {}",
+                    attachment.id, url, err
+                ))
+            })?
         };
-        self.put(&url_str)
-            .body(surf::Body::from_json(data).unwrap())
+        let mut res = self
+            .execute_accepting(
+                || {
+                    let req = surf::RequestBuilder::new(Method::Get, url.clone());
+                    Ok(if self.is_aha_url(&url) {
+                        self.add_headers(req)
+                    } else {
+                        req
+                    })
+                },
+                |status| status.is_success() || status.is_redirection(),
+            )
+            .await?;
+        if res.status().is_redirection() {
+            let location = res
+                .header("Location")
+                .map(|values| values.last().as_str().to_string())
+                .ok_or_else(|| {
+                    Error::Validation(format!(
+                        "attachment {} redirected without a location",
+                        attachment.id
+                    ))
+                })?;
+            // the location may be relative to the url that redirected
+            let location = url.join(&location).map_err(|err| {
+                Error::Validation(format!(
+                    "attachment {} redirected to an invalid location {}: {}",
+                    attachment.id, location, err
+                ))
+            })?;
+            res = self
+                .execute(|| Ok(surf::RequestBuilder::new(Method::Get, location.clone())))
+                .await?;
+        }
+        Ok(res.body_bytes().await?)
+    }
+
+    pub async fn list_requirements_for_feature(
+        &self,
+        feature_id: &str,
+        paging: &Paging,
+    ) -> Result<RequirementList> {
+        let url_str = format!("/api/v1/features/{}/requirements", feature_id);
+        self.list(&url_str, &[], paging).await
+    }
+
+    pub async fn create_requirement(
+        &self,
+        feature_id: &str,
+        requirement: &NewRequirement,
+    ) -> Result<Option<Requirement>> {
+        #[derive(Serialize)]
+        struct Requirement<'a> {
+            requirement: &'a NewRequirement,
+        }
+
+        if requirement.name.trim().is_empty() {
+            return Err(Error::Validation("a requirement needs a name".into()));
+        }
+        let url_str = format!("/api/v1/features/{}/requirements", feature_id);
+        let body = serde_json::to_string(&Requirement { requirement })?;
+        let res: Option<RequirementResponse> = self
+            .send_saved(|| Ok(self.post(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.requirement))
+    }
+
+    pub async fn update_requirement(
+        &self,
+        requirement_id: &str,
+        requirement: &RequirementUpdate,
+    ) -> Result<Option<Requirement>> {
+        #[derive(Serialize)]
+        struct Requirement<'a> {
+            requirement: &'a RequirementUpdate,
+        }
+
+        if requirement.is_empty() {
+            return Err(Error::Validation(
+                "nothing to update for requirement".into(),
+            ));
+        }
+        let url_str = format!("/api/v1/requirements/{}", requirement_id);
+        let body = serde_json::to_string(&Requirement { requirement })?;
+        let res: Option<RequirementResponse> = self
+            .send_saved(|| Ok(self.put(&url_str)?.body(json_body(&body))))
+            .await?;
+        Ok(res.map(|res| res.requirement))
+    }
+
+    /// Moves the given requirements to positions 1, 2, 3… in the order they
+    /// are passed. aha.io has no bulk endpoint, so each one is updated in turn.
+    pub async fn reorder_requirements(
+        &self,
+        requirement_ids: &[String],
+    ) -> Result<Vec<Requirement>> {
+        if requirement_ids.is_empty() {
+            return Err(Error::Validation("no requirements to reorder".into()));
+        }
+        let mut requirements = Vec::with_capacity(requirement_ids.len());
+        for (i, requirement_id) in requirement_ids.iter().enumerate() {
+            let update = RequirementUpdate {
+                position: Some(i as i64 + 1),
+                ..RequirementUpdate::default()
+            };
+            let requirement = match self.update_requirement(requirement_id, &update).await? {
+                Some(requirement) => requirement,
+                None => self.get_requirement(requirement_id).await?,
+            };
+            requirements.push(requirement);
+        }
+        Ok(requirements)
+    }
+
+    pub async fn get_requirement(&self, requirement_id: &str) -> Result<Requirement> {
+        let url_str = format!("/api/v1/requirements/{}", requirement_id);
+        let res: RequirementResponse = self.send(|| self.get(&url_str)).await?;
+        Ok(res.requirement)
+    }
+
+    pub async fn delete_requirement(&self, requirement_id: &str) -> Result<()> {
+        let url_str = format!("/api/v1/requirements/{}", requirement_id);
+        self.execute(|| self.delete(&url_str)).await?;
+        Ok(())
     }
 }
@@ -0,0 +1,189 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The blimey config file, normally `$XDG_CONFIG_HOME/blimey/config.toml`.
+///
+/// ```toml
+/// default_profile = "production"
+///
+/// [profiles.production]
+/// subdomain = "acme"
+/// token = { env = "ACME_AHA_TOKEN" }
+/// format = "table"
+/// product = "PROD"
+///
+/// [profiles.sandbox]
+/// subdomain = "acme-sandbox"
+/// token = { command = "pass show aha/sandbox" }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Config {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// The settings for one aha.io account.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Profile {
+    pub subdomain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<TokenSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+}
+
+impl Profile {
+    /// Checks the base url, when the profile has one, is an absolute http or
+    /// https url such as `https://acme.aha.io`.
+    pub fn check_base_url(&self) -> Result<()> {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url,
+            None => return Ok(()),
+        };
+        let url = surf::Url::parse(base_url).map_err(|err| {
+            Error::Config(format!(
+                "base url '{}' is not a valid url: {}",
+                base_url, err
+            ))
+        })?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(Error::Config(format!(
+                "base url '{}' must start with http:// or https://",
+                base_url
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Where a profile's API token comes from. Storing the token itself is
+/// supported, but reading it from the environment or a password manager
+/// keeps it out of the config file.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenSource {
+    Value(String),
+    Env(String),
+    Command(String),
+}
+
+impl TokenSource {
+    pub fn resolve(&self) -> Result<String> {
+        match self {
+            TokenSource::Value(token) => Ok(token.clone()),
+            TokenSource::Env(var) => std::env::var(var)
+                .map_err(|_| Error::Config(format!("environment variable {} is not set", var))),
+            TokenSource::Command(cmd) => {
+                let output = Command::new("sh")
+                    .arg("-c")
+                    .arg(cmd)
+                    .output()
+                    .map_err(|err| Error::Config(format!("could not run '{}': {}", cmd, err)))?;
+                if !output.status.success() {
+                    return Err(Error::Config(format!(
+                        "'{}' exited with {}",
+                        cmd, output.status
+                    )));
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            }
+        }
+    }
+}
+
+impl Config {
+    /// `$BLIMEY_CONFIG` if set, otherwise `blimey/config.toml` in the
+    /// platform's config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        match std::env::var_os("BLIMEY_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|dir| dir.join("blimey").join("config.toml")),
+        }
+    }
+
+    /// Reads the config file, treating a missing file as an empty config.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(Error::Config(format!(
+                    "could not read {}: {}",
+                    path.display(),
+                    err
+                )))
+            }
+        };
+        toml::from_str(&contents)
+            .map_err(|err| Error::Config(format!("invalid config {}: {}", path.display(), err)))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self)
+            .map_err(|err| Error::Config(format!("could not write config: {}", err)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| {
+                Error::Config(format!("could not create {}: {}", dir.display(), err))
+            })?;
+        }
+        let write_err = |err: std::io::Error| {
+            Error::Config(format!("could not write {}: {}", path.display(), err))
+        };
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // profiles may hold a token, keep the file private from the start
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path).map_err(write_err)?;
+        // an existing file keeps its mode on open, tighten it before writing
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))
+                .map_err(|err| {
+                    Error::Config(format!("could not protect {}: {}", path.display(), err))
+                })?;
+        }
+        file.write_all(contents.as_bytes()).map_err(write_err)?;
+        Ok(())
+    }
+
+    /// The profile named `name`, or the default profile when `name` is
+    /// `None`. Asking for a profile that doesn't exist is an error, having
+    /// no default profile is not.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<&Profile>> {
+        match name.or(self.default_profile.as_deref()) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| Error::Config(format!("no profile named '{}'", name))),
+            None => Ok(None),
+        }
+    }
+
+    pub fn remove_profile(&mut self, name: &str) -> Result<Profile> {
+        let profile = self
+            .profiles
+            .remove(name)
+            .ok_or_else(|| Error::Config(format!("no profile named '{}'", name)))?;
+        if self.default_profile.as_deref() == Some(name) {
+            self.default_profile = None;
+        }
+        Ok(profile)
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent or the response could not be read.
+    Transport(surf::Error),
+    /// aha.io answered with a non-success status code.
+    Http {
+        status: surf::StatusCode,
+        message: String,
+    },
+    /// A request or response body was not the JSON we expected.
+    Serialization(serde_json::Error),
+    /// The arguments were rejected before anything was sent to aha.io.
+    Validation(String),
+    /// The config file or a profile in it could not be used.
+    Config(String),
+    /// A local file or directory could not be written.
+    Io {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl Error {
+    /// Builds an `Error::Http` from a response body, preferring the message
+    /// aha.io puts in its `error`/`errors` fields over the raw body.
+    pub(crate) fn from_response(status: surf::StatusCode, body: &str) -> Self {
+        let message = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|json| error_message(&json))
+            .unwrap_or_else(|| body.trim().to_string());
+        Error::Http { status, message }
+    }
+
+    pub fn status(&self) -> Option<surf::StatusCode> {
+        match self {
+            Error::Http { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+fn error_message(json: &serde_json::Value) -> Option<String> {
+    let error = json
+        .get("errors")
+        .or_else(|| json.get("error"))
+        .or_else(|| json.get("message"))?;
+    match error {
+        serde_json::Value::String(message) => Some(message.clone()),
+        serde_json::Value::Array(errors) => {
+            let messages: Vec<String> = errors.iter().filter_map(error_message_value).collect();
+            Some(messages.join(", ")).filter(|m| !m.is_empty())
+        }
+        serde_json::Value::Object(_) => error_message(error),
+        _ => None,
+    }
+}
+
+fn error_message_value(json: &serde_json::Value) -> Option<String> {
+    match json {
+        serde_json::Value::String(message) => Some(message.clone()),
+        _ => error_message(json),
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "request failed: {}", err),
+            Error::Http { status, message } if message.is_empty() => {
+                write!(
+                    f,
+                    "aha.io returned {} {}",
+                    status,
+                    status.canonical_reason()
+                )
+            }
+            Error::Http { status, message } => write!(
+                f,
+                "aha.io returned {} {}: {}",
+                status,
+                status.canonical_reason(),
+                message
+            ),
+            Error::Serialization(err) => write!(f, "invalid JSON: {}", err),
+            Error::Validation(message) => write!(f, "{}", message),
+            Error::Config(message) => write!(f, "config: {}", message),
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Serialization(err) => Some(err),
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<surf::Error> for Error {
+    fn from(err: surf::Error) -> Self {
+        Error::Transport(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Serialization(err)
+    }
+}
@@ -1 +1,8 @@
 pub mod aha;
+pub mod config;
+mod error;
+pub mod models;
+pub mod output;
+pub mod retry;
+
+pub use error::{Error, Result};
@@ -1,5 +1,19 @@
-use blimey::aha::AhaRequest;
+use blimey::aha::{AhaRequest, Paging};
+use blimey::config::{self, Profile, TokenSource};
+use blimey::models::{
+    CustomFieldKind, EpicUpdate, FeatureFilters, FeatureUpdate, IdeaFilters, LinkType, LinkedIssue,
+    LinkedRecord, NewEndorsement, NewEpic, NewFeature, NewIdea, NewReleasePhase, NewRequirement,
+    NewResearchRecord, NewStrategy, NewTimeTrackingEvent, NewTodo, Paginated, RecordKind,
+    ReleasePhaseUpdate, RequirementUpdate, ResearchRecordUpdate, StrategyUpdate, WorkAmount,
+};
+use blimey::output::{Format, Output, Record};
+use blimey::retry::RetryPolicy;
+use blimey::{Error, Result};
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
+use terminal_size::{terminal_size_of, Width};
 
 #[derive(StructOpt, Debug)]
 /// Blimey!
@@ -7,22 +21,61 @@ use structopt::StructOpt;
 /// Use blimey to meet all your aha.io needs.
 #[structopt(name = "blimey", about = "A cli for aha.io", author)]
 struct Cli {
+    /// The config profile to use instead of the default profile. A named profile
+    /// takes its subdomain, token and product from the profile only, ignoring
+    /// --subdomain, --token and their environment variables
+    #[structopt(short = "P", long, env = "BLIMEY_PROFILE")]
+    profile: Option<String>,
+
     /// This is your aha subdomain: <subdomain>.aha.io
     #[structopt(short, long, env = "BLIMEY_AHA_SUBDOMAIN")]
-    subdomain: String,
+    subdomain: Option<String>,
 
     /// Generate an API token from your aha.io account
-    #[structopt(short, long, env = "BLIMEY_AHA_TOKEN")]
-    token: String,
+    #[structopt(short, long, env = "BLIMEY_AHA_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// How many times to retry a request aha.io rate limits before giving up
+    #[structopt(long, default_value = "5", env = "BLIMEY_MAX_RETRIES")]
+    max_retries: u32,
 
-    /// Output format. JSON is the only supported option right now
-    #[structopt(short, long, default_value = "json", env = "BLIMEY_FORMAT")]
-    format: String,
+    /// Output format: json, ndjson, csv or table [default: json]
+    #[structopt(short, long, env = "BLIMEY_FORMAT")]
+    format: Option<String>,
+
+    /// Comma separated columns for table and csv output, e.g. reference_num,name,workflow_status.name
+    #[structopt(long, use_delimiter = true, env = "BLIMEY_COLUMNS")]
+    columns: Option<Vec<String>>,
 
     #[structopt(subcommand)]
     subcommands: Option<Aha>,
 }
 
+#[derive(StructOpt, Debug)]
+struct PageArgs {
+    /// Page number to fetch (optional)
+    #[structopt(long)]
+    page: Option<u64>,
+
+    /// Number of records to fetch per page (optional)
+    #[structopt(long)]
+    per_page: Option<u64>,
+
+    /// Fetch every page and merge the results
+    #[structopt(long, conflicts_with = "page")]
+    all: bool,
+}
+
+impl PageArgs {
+    fn paging(&self) -> Paging {
+        Paging {
+            page: self.page,
+            per_page: self.per_page,
+            all: self.all,
+        }
+    }
+}
+
 #[derive(StructOpt, Debug)]
 enum Aha {
     /// Create, get, list, and update aha.io products (workspaces)
@@ -31,17 +84,131 @@ enum Aha {
         commands: Option<Product>,
     },
 
-    /// Create, get, list, and update aha.io releases for a given product
+    /// Create, get, list, update, and delete aha.io releases for a given product
     Release {
         #[structopt(subcommand)]
         commands: Option<Release>,
     },
 
-    /// Create, get, list, and update aha.io features
+    /// List, get, create, update, and delete the phases and milestones of a release
+    ReleasePhase {
+        #[structopt(subcommand)]
+        commands: Option<ReleasePhase>,
+    },
+
+    /// List, get, create, update, and link aha.io initiatives
+    Initiative {
+        #[structopt(subcommand)]
+        commands: Option<Initiative>,
+    },
+
+    /// List, get, create, update, and link aha.io goals
+    Goal {
+        #[structopt(subcommand)]
+        commands: Option<Goal>,
+    },
+
+    /// List, get, create, and update the personas of a product
+    Persona {
+        #[structopt(subcommand)]
+        commands: Option<Persona>,
+    },
+
+    /// List, get, create, and update the competitors of a product
+    Competitor {
+        #[structopt(subcommand)]
+        commands: Option<Competitor>,
+    },
+
+    /// Create, get, list, update, and delete aha.io features
     Feature {
         #[structopt(subcommand)]
         commands: Option<Feature>,
     },
+
+    /// List, get, create, and update aha.io epics and list their features
+    Epic {
+        #[structopt(subcommand)]
+        commands: Option<Epic>,
+    },
+
+    /// List, get, create, and vote on aha.io ideas
+    Idea {
+        #[structopt(subcommand)]
+        commands: Option<Idea>,
+    },
+
+    /// List, get, create, update, reorder, and delete aha.io requirements
+    Requirement {
+        #[structopt(subcommand)]
+        commands: Option<Requirement>,
+    },
+
+    /// List and add comments on features, requirements, ideas, epics, and releases
+    Comment {
+        #[structopt(subcommand)]
+        commands: Option<Comment>,
+    },
+
+    /// List, add, replace, and remove tags on features, ideas, and epics
+    Tag {
+        #[structopt(subcommand)]
+        commands: Option<Tag>,
+    },
+
+    /// List, upload, and download attachments of features, requirements, ideas, and comments
+    Attachment {
+        #[structopt(subcommand)]
+        commands: Option<Attachment>,
+    },
+
+    /// List and log time tracked on features and requirements
+    Time {
+        #[structopt(subcommand)]
+        commands: Option<Time>,
+    },
+
+    /// List, create, and delete links between features, requirements, ideas, epics, and releases
+    Link {
+        #[structopt(subcommand)]
+        commands: Option<Link>,
+    },
+
+    /// List and set the integration fields linking features and requirements to issues
+    Integration {
+        #[structopt(subcommand)]
+        commands: Option<Integration>,
+    },
+
+    /// List custom field definitions and set custom fields on records
+    Field {
+        #[structopt(subcommand)]
+        commands: Option<Field>,
+    },
+
+    /// List the workflows of a product and their statuses
+    Workflow {
+        #[structopt(subcommand)]
+        commands: Option<Workflow>,
+    },
+
+    /// List and get aha.io users, for the account or a product
+    User {
+        #[structopt(subcommand)]
+        commands: Option<User>,
+    },
+
+    /// List, create, and complete aha.io to-dos
+    Todo {
+        #[structopt(subcommand)]
+        commands: Option<Todo>,
+    },
+
+    /// List, add, and remove profiles in the blimey config file
+    Config {
+        #[structopt(subcommand)]
+        commands: Option<Config>,
+    },
 }
 
 #[derive(StructOpt, Debug)]
@@ -51,12 +218,15 @@ enum Product {
         /// Only return product/workspaces updated since this date
         #[structopt(short, long)]
         updated_since: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
     },
     /// Get a product by name or id
     Get {
-        /// Product name or id
+        /// Product name or id, defaults to the profile's product
         #[structopt(short, long)]
-        product_id: String,
+        product_id: Option<String>,
     },
     /// Create a new product/workspace
     Create {
@@ -78,9 +248,9 @@ enum Product {
     },
     /// Update an existing product
     Update {
-        /// Product name or id
+        /// Product name or id, defaults to the profile's product
         #[structopt(short, long)]
-        product_id: String,
+        product_id: Option<String>,
 
         /// Updated product name (optional)
         #[structopt(short, long)]
@@ -100,9 +270,12 @@ enum Product {
 enum Release {
     /// List all releases for a given product
     List {
-        /// Product name or id
+        /// Product name or id, defaults to the profile's product
         #[structopt(short, long)]
-        product_id: String,
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
     },
     /// Get a release
     Get {
@@ -116,15 +289,15 @@ enum Release {
         #[structopt(short, long)]
         name: String,
 
-        /// Product id this release belongs to
+        /// Product id this release belongs to, defaults to the profile's product
         #[structopt(short, long)]
-        product_id: String,
+        product_id: Option<String>,
     },
     /// Update a release for a given product
     Update {
-        /// Product name or id
+        /// Product name or id, defaults to the profile's product
         #[structopt(short, long)]
-        product_id: String,
+        product_id: Option<String>,
 
         /// Release name or id
         #[structopt(short = "r", long = "release-id")]
@@ -138,132 +311,2858 @@ enum Release {
         #[structopt(short = "u", long = "rollup-release-id")]
         parent_id: Option<String>,
     },
+    /// Delete a release
+    Delete {
+        /// Release name or id
+        #[structopt(short = "r", long = "release-id")]
+        release_id: String,
+
+        /// Delete without asking for confirmation
+        #[structopt(short, long)]
+        yes: bool,
+    },
 }
 
 #[derive(StructOpt, Debug)]
-enum Feature {
-    /// List all features for a given product
+enum ReleasePhase {
+    /// List the phases and milestones of a release
     List {
-        /// Product name or id
-        #[structopt(short, long)]
-        product_id: String,
+        /// Release name or id
+        #[structopt(short = "r", long = "release-id")]
+        release_id: String,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
     },
-    /// Get a feature by name or id
+    /// Get a release phase by id
     Get {
-        /// Feature name or id
+        /// Release phase id
         #[structopt(short, long)]
-        feature_id: String,
+        phase_id: String,
     },
-    /// Update a feature
+    /// Add a phase or milestone to a release
+    Create {
+        /// Release name or id
+        #[structopt(short = "r", long = "release-id")]
+        release_id: String,
+
+        /// Phase name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Start date, format: YYYY-MM-DD
+        #[structopt(short, long = "start")]
+        start_on: String,
+
+        /// End date, format: YYYY-MM-DD, a milestone ends on its start date when left out
+        #[structopt(short, long = "end")]
+        end_on: Option<String>,
+
+        /// Create a milestone instead of a phase
+        #[structopt(short, long)]
+        milestone: bool,
+
+        /// Phase description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+    },
+    /// Update a release phase, e.g. to move its dates
     Update {
-        /// Feature name or id
+        /// Release phase id
         #[structopt(short, long)]
-        feature_id: String,
+        phase_id: String,
 
-        /// The updated feature name (optional)
+        /// The updated phase name (optional)
         #[structopt(short, long)]
         name: Option<String>,
 
         /// The updated start date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long = "start")]
+        start_on: Option<String>,
+
+        /// The updated end date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long = "end")]
+        end_on: Option<String>,
+
+        /// The updated description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+    },
+    /// Delete a release phase
+    Delete {
+        /// Release phase id
         #[structopt(short, long)]
-        start_date: Option<String>,
+        phase_id: String,
 
-        /// The updated due date, format: YYYY-MM-DD (optional)
+        /// Delete without asking for confirmation
         #[structopt(short, long)]
-        due_date: Option<String>,
+        yes: bool,
     },
 }
 
-#[async_std::main]
-async fn main() -> surf::Result<()> {
-    let args = Cli::from_args();
-    let req = get_request(&args.token, &args.subdomain, &args.subcommands);
-    let mut res = req.await?;
-    assert_eq!(res.status(), http_types::StatusCode::Ok);
-    if args.format == "json" {
-        println!("{}", res.body_string().await?);
-    }
-    Ok(())
+#[derive(StructOpt, Debug)]
+enum Persona {
+    /// List all personas for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a persona by id, with its custom fields
+    Get {
+        /// Persona id
+        #[structopt(short, long)]
+        persona_id: String,
+    },
+    /// Create a new persona for a given product
+    Create {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Persona name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Persona description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Set a custom field, e.g. --field age_range=25-34; repeat for more fields
+        #[structopt(long = "field", number_of_values = 1, parse(try_from_str = parse_field))]
+        fields: Vec<(String, String)>,
+    },
+    /// Update a persona
+    Update {
+        /// Persona id
+        #[structopt(short, long)]
+        persona_id: String,
+
+        /// The updated persona name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Set a custom field, e.g. --field age_range=25-34; repeat for more fields
+        #[structopt(long = "field", number_of_values = 1, parse(try_from_str = parse_field))]
+        fields: Vec<(String, String)>,
+    },
 }
 
-fn get_request(token: &str, subdomain: &str, subcommands: &Option<Aha>) -> surf::RequestBuilder {
-    let aha_request = AhaRequest::new(token, subdomain);
-    if let Some(scmd) = subcommands {
-        match scmd {
-            Aha::Product { commands } => {
-                if let Some(cmd) = commands {
-                    match cmd {
-                        Product::List { updated_since } => {
-                            return aha_request.list_products(updated_since)
-                        }
-                        Product::Get { product_id } => return aha_request.get_product(product_id),
-                        Product::Create {
-                            name,
-                            prefix,
-                            parent_id,
-                            workspace_type,
-                        } => {
-                            return aha_request.create_product(
-                                name,
-                                prefix,
-                                parent_id,
-                                workspace_type,
-                            )
-                        }
-                        Product::Update {
-                            product_id,
-                            name,
-                            prefix,
-                            parent_id,
-                        } => {
-                            return aha_request.update_product(product_id, name, prefix, parent_id)
-                        }
-                    }
-                }
-            }
-            Aha::Release { commands } => {
-                if let Some(releasecmd) = commands {
-                    match releasecmd {
-                        Release::List { product_id } => {
-                            return aha_request.list_releases_for_product(product_id)
-                        }
-                        Release::Get { release_id } => return aha_request.get_release(release_id),
-                        Release::Create { product_id, name } => {
-                            return aha_request.create_release_for_product(product_id, name)
-                        }
-                        Release::Update {
-                            product_id,
-                            release_id,
-                            name,
-                            parent_id,
-                        } => {
-                            return aha_request.update_release_for_product(
-                                product_id, release_id, name, parent_id,
-                            )
-                        }
-                    }
-                }
-            }
-            Aha::Feature { commands } => {
-                if let Some(featurecmd) = commands {
-                    match featurecmd {
-                        Feature::List { product_id } => {
-                            return aha_request.list_features_for_product(product_id)
-                        }
-                        Feature::Get { feature_id } => return aha_request.get_feature(feature_id),
-                        Feature::Update {
-                            feature_id,
-                            name,
-                            start_date,
-                            due_date,
-                        } => {
-                            return aha_request
-                                .update_feature(feature_id, name, start_date, due_date)
-                        }
-                    }
-                }
-            }
+#[derive(StructOpt, Debug)]
+enum Competitor {
+    /// List all competitors for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a competitor by id, with its custom fields
+    Get {
+        /// Competitor id
+        #[structopt(short, long)]
+        competitor_id: String,
+    },
+    /// Create a new competitor for a given product
+    Create {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Competitor name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Competitor description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Set a custom field, e.g. --field pricing=freemium; repeat for more fields
+        #[structopt(long = "field", number_of_values = 1, parse(try_from_str = parse_field))]
+        fields: Vec<(String, String)>,
+    },
+    /// Update a competitor
+    Update {
+        /// Competitor id
+        #[structopt(short, long)]
+        competitor_id: String,
+
+        /// The updated competitor name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Set a custom field, e.g. --field pricing=freemium; repeat for more fields
+        #[structopt(long = "field", number_of_values = 1, parse(try_from_str = parse_field))]
+        fields: Vec<(String, String)>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Initiative {
+    /// List all initiatives for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get an initiative by reference or id
+    Get {
+        /// Initiative reference or id
+        #[structopt(short, long)]
+        initiative_id: String,
+    },
+    /// Create a new initiative for a given product
+    Create {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Initiative name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Initiative description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+    },
+    /// Update the name, status or progress of an initiative
+    Update {
+        /// Initiative reference or id
+        #[structopt(short, long)]
+        initiative_id: String,
+
+        /// The updated initiative name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// The updated progress in percent, for manually tracked progress (optional)
+        #[structopt(long)]
+        progress: Option<f64>,
+    },
+    /// Link a feature or an epic to an initiative
+    Link {
+        /// Initiative reference or id
+        #[structopt(short, long)]
+        initiative_id: String,
+
+        /// Feature reference or id to link
+        #[structopt(short, long, required_unless = "epic", conflicts_with = "epic")]
+        feature: Option<String>,
+
+        /// Epic reference or id to link
+        #[structopt(short, long)]
+        epic: Option<String>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Goal {
+    /// List all goals for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a goal by reference or id
+    Get {
+        /// Goal reference or id
+        #[structopt(short = "g", long)]
+        goal_id: String,
+    },
+    /// Create a new goal for a given product
+    Create {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Goal name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Goal description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+    },
+    /// Update the name, status or progress of a goal
+    Update {
+        /// Goal reference or id
+        #[structopt(short = "g", long)]
+        goal_id: String,
+
+        /// The updated goal name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// The updated progress in percent, for manually tracked progress (optional)
+        #[structopt(long)]
+        progress: Option<f64>,
+    },
+    /// Link a feature or an epic to a goal
+    Link {
+        /// Goal reference or id
+        #[structopt(short = "g", long)]
+        goal_id: String,
+
+        /// Feature reference or id to link
+        #[structopt(short, long, required_unless = "epic", conflicts_with = "epic")]
+        feature: Option<String>,
+
+        /// Epic reference or id to link
+        #[structopt(short, long)]
+        epic: Option<String>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Feature {
+    /// List all features for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Only return features with this tag
+        #[structopt(long)]
+        tag: Option<String>,
+
+        /// Only return features assigned to this user email or id
+        #[structopt(short, long)]
+        assignee: Option<String>,
+
+        /// Only return features updated since this date
+        #[structopt(short, long)]
+        updated_since: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a feature by name or id
+    Get {
+        /// Feature name or id
+        #[structopt(short, long)]
+        feature_id: String,
+
+        /// Include the feature's requirements
+        #[structopt(long)]
+        requirements: bool,
+    },
+    /// Find the feature linked to an issue key, e.g. a Jira key
+    Find {
+        /// Issue key stored by the integration, e.g. JIRA-123
+        key: String,
+
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+    },
+    /// Create a new feature in a release
+    Create {
+        /// Release name or id the feature belongs to
+        #[structopt(short, long)]
+        release_id: String,
+
+        /// Feature name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Feature description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// Email or id of the user to assign the feature to (optional)
+        #[structopt(short, long)]
+        assignee: Option<String>,
+
+        /// Comma separated tags (optional)
+        #[structopt(long, use_delimiter = true)]
+        tags: Vec<String>,
+
+        /// Start date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        start_date: Option<String>,
+
+        /// Due date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        due_date: Option<String>,
+
+        /// Initial estimate, e.g. 3d, 4h or 5p for points (optional)
+        #[structopt(short = "e", long)]
+        initial_estimate: Option<String>,
+    },
+    /// Update a feature
+    Update {
+        /// Feature name or id
+        #[structopt(short, long)]
+        feature_id: String,
+
+        /// The updated feature name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated start date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        start_date: Option<String>,
+
+        /// The updated due date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        due_date: Option<String>,
+
+        /// Move the feature to this status of its workflow, by name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// The original estimate, e.g. 3d, 4h or 5p for points (optional)
+        #[structopt(long)]
+        original_estimate: Option<String>,
+
+        /// The remaining estimate, e.g. 3d, 4h or 5p for points (optional)
+        #[structopt(long)]
+        remaining_estimate: Option<String>,
+
+        /// The work done so far, e.g. 3d, 4h or 5p for points (optional)
+        #[structopt(long)]
+        work_done: Option<String>,
+
+        /// Set a custom field, e.g. --field score=8; repeat for more fields
+        #[structopt(long = "field", number_of_values = 1, parse(try_from_str = parse_field))]
+        fields: Vec<(String, String)>,
+    },
+    /// Delete a feature
+    Delete {
+        /// Feature name or id
+        #[structopt(short, long)]
+        feature_id: String,
+
+        /// Delete without asking for confirmation
+        #[structopt(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Epic {
+    /// List all epics for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get an epic by reference or id, with the progress of its features
+    Get {
+        /// Epic reference or id
+        #[structopt(short, long)]
+        epic_id: String,
+    },
+    /// Create a new epic in a release
+    Create {
+        /// Release name or id the epic belongs to
+        #[structopt(short, long)]
+        release_id: String,
+
+        /// Epic name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Epic description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// Email or id of the user to assign the epic to (optional)
+        #[structopt(short, long)]
+        assignee: Option<String>,
+
+        /// Comma separated tags (optional)
+        #[structopt(long, use_delimiter = true)]
+        tags: Vec<String>,
+
+        /// Start date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        start_date: Option<String>,
+
+        /// Due date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        due_date: Option<String>,
+    },
+    /// Update an epic
+    Update {
+        /// Epic reference or id
+        #[structopt(short, long)]
+        epic_id: String,
+
+        /// The updated epic name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated description (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// The updated workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// Email or id of the user to assign the epic to (optional)
+        #[structopt(short, long)]
+        assignee: Option<String>,
+
+        /// The updated start date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        start_date: Option<String>,
+
+        /// The updated due date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        due_date: Option<String>,
+    },
+    /// List the features of an epic
+    Features {
+        /// Epic reference or id
+        #[structopt(short, long)]
+        epic_id: String,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Idea {
+    /// List ideas for a given product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Only return ideas with this workflow status name or id
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// Only return ideas in this category name or id
+        #[structopt(long)]
+        category: Option<String>,
+
+        /// Only return ideas with this tag
+        #[structopt(long)]
+        tag: Option<String>,
+
+        /// Only return ideas updated since this date
+        #[structopt(short, long)]
+        updated_since: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get an idea by reference or id
+    Get {
+        /// Idea reference or id
+        #[structopt(short, long)]
+        idea_id: String,
+    },
+    /// Create a new idea for a given product
+    Create {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        /// Idea name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Idea description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Comma separated category names (optional)
+        #[structopt(long, use_delimiter = true)]
+        categories: Vec<String>,
+
+        /// Comma separated tags (optional)
+        #[structopt(long, use_delimiter = true)]
+        tags: Vec<String>,
+
+        /// Workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+    },
+    /// Vote on an idea on behalf of a portal user and their organization
+    Vote {
+        /// Idea reference or id
+        #[structopt(short, long)]
+        idea_id: String,
+
+        /// Email of the portal user to vote for
+        #[structopt(short, long)]
+        email: String,
+
+        /// Organization name or id to vote for (optional)
+        #[structopt(short, long)]
+        organization: Option<String>,
+
+        /// Number of votes to add (optional)
+        #[structopt(short, long)]
+        value: Option<i64>,
+
+        /// Why the organization wants this idea (optional)
+        #[structopt(long)]
+        description: Option<String>,
+    },
+    /// List the comments on an idea
+    Comments {
+        /// Idea reference or id
+        #[structopt(short, long)]
+        idea_id: String,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Requirement {
+    /// List the requirements of a feature
+    List {
+        /// Feature name or id
+        #[structopt(short, long)]
+        feature_id: String,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a requirement by reference or id
+    Get {
+        /// Requirement reference or id
+        #[structopt(short, long)]
+        requirement_id: String,
+    },
+    /// Create a new requirement for a feature
+    Create {
+        /// Feature name or id the requirement belongs to
+        #[structopt(short, long)]
+        feature_id: String,
+
+        /// Requirement name
+        #[structopt(short, long)]
+        name: String,
+
+        /// Requirement description, html is allowed (optional)
+        #[structopt(long)]
+        description: Option<String>,
+
+        /// Workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// Email or id of the user to assign the requirement to (optional)
+        #[structopt(short, long)]
+        assignee: Option<String>,
+    },
+    /// Update a requirement
+    Update {
+        /// Requirement reference or id
+        #[structopt(short, long)]
+        requirement_id: String,
+
+        /// The updated requirement name (optional)
+        #[structopt(short, long)]
+        name: Option<String>,
+
+        /// The updated workflow status name or id (optional)
+        #[structopt(long)]
+        status: Option<String>,
+
+        /// Email or id of the user to assign the requirement to (optional)
+        #[structopt(short, long)]
+        assignee: Option<String>,
+    },
+    /// Reorder requirements, the first one given moves to the top
+    Reorder {
+        /// Requirement references or ids in their new order
+        #[structopt(required = true)]
+        requirement_ids: Vec<String>,
+    },
+    /// Delete a requirement
+    Delete {
+        /// Requirement name or id
+        #[structopt(short, long)]
+        requirement_id: String,
+
+        /// Delete without asking for confirmation
+        #[structopt(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Comment {
+    /// List the comments on a record
+    List {
+        /// Record reference or id, e.g. PROD-12, PROD-12-1, PROD-I-3, PROD-E-4 or PROD-R-5
+        record_id: String,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic or release
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Add a comment, written in Markdown, to a record
+    Add {
+        /// Record reference or id, e.g. PROD-12, PROD-12-1, PROD-I-3, PROD-E-4 or PROD-R-5
+        record_id: String,
+
+        /// Comment body; read from stdin when it is - or neither it nor --file is given
+        #[structopt(conflicts_with = "file")]
+        body: Option<String>,
+
+        /// Read the comment body from this file
+        #[structopt(long, parse(from_os_str))]
+        file: Option<PathBuf>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic or release
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+
+        /// Send the body as html instead of converting it from Markdown
+        #[structopt(long)]
+        html: bool,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Tag {
+    /// List the tags of a record
+    List {
+        /// Record reference or id, e.g. PROD-12, PROD-I-3 or PROD-E-4
+        record_id: String,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, idea or epic
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Add tags to a record, keeping its other tags
+    Add {
+        /// Record reference or id, e.g. PROD-12, PROD-I-3 or PROD-E-4
+        record_id: String,
+
+        /// Tags to add
+        #[structopt(required = true)]
+        tags: Vec<String>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, idea or epic
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Replace all tags of a record, giving no tags removes them all
+    Set {
+        /// Record reference or id, e.g. PROD-12, PROD-I-3 or PROD-E-4
+        record_id: String,
+
+        /// The record's new tags
+        tags: Vec<String>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, idea or epic
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Remove tags from a record
+    Remove {
+        /// Record reference or id, e.g. PROD-12, PROD-I-3 or PROD-E-4
+        record_id: String,
+
+        /// Tags to remove
+        #[structopt(required = true)]
+        tags: Vec<String>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, idea or epic
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Attachment {
+    /// List the attachments of a record
+    List {
+        /// Record reference or id, e.g. PROD-12, PROD-12-1 or PROD-I-3
+        record_id: String,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic, release or comment
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Upload local files as attachments of a record
+    Upload {
+        /// Record reference or id, e.g. PROD-12, PROD-12-1 or PROD-I-3
+        record_id: String,
+
+        /// Files to upload
+        #[structopt(required = true, parse(from_os_str))]
+        files: Vec<PathBuf>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic, release or comment
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Download all attachments of a record into a directory
+    Download {
+        /// Record reference or id, e.g. PROD-12, PROD-12-1 or PROD-I-3
+        record_id: String,
+
+        /// Directory to save the files in, created if missing
+        #[structopt(short, long, parse(from_os_str), default_value = ".")]
+        output_dir: PathBuf,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic, release or comment
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Time {
+    /// List the time logged on a feature or requirement
+    List {
+        /// Record reference or id, e.g. PROD-12 or PROD-12-1
+        record_id: String,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature or requirement
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Log time on a feature or requirement, e.g. blimey time log PROD-123 2h "pairing"
+    Log {
+        /// Record reference or id, e.g. PROD-12 or PROD-12-1
+        record_id: String,
+
+        /// Work done, e.g. 2h, 1h30m, 1d (8 hours) or 3p for points
+        amount: WorkAmount,
+
+        /// What the time was spent on (optional)
+        description: Option<String>,
+
+        /// The day the work was done, format: YYYY-MM-DD, aha.io uses today when left out
+        #[structopt(short, long)]
+        date: Option<String>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature or requirement
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Link {
+    /// List the links of a record
+    List {
+        /// Record reference or id, e.g. PROD-12, PROD-12-1, PROD-I-3, PROD-E-4 or PROD-R-5
+        record_id: String,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic or release
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Link two records, e.g. blimey link create PROD-12 "depends on" PROD-9
+    Create {
+        /// Record reference or id the link starts from
+        record_id: String,
+
+        /// How the records are linked: "relates to", "depends on" or "duplicates"
+        link_type: LinkType,
+
+        /// Record reference or id the link points to
+        other_id: String,
+
+        /// Type of the first record, needed when it can't be told from the reference
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+
+        /// Type of the second record, needed when it can't be told from the reference
+        #[structopt(long = "other-type")]
+        other_kind: Option<RecordKind>,
+    },
+    /// Delete a record link
+    Delete {
+        /// Record link id
+        link_id: String,
+
+        /// Delete without asking for confirmation
+        #[structopt(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Integration {
+    /// List the integration fields of a feature or requirement
+    List {
+        /// Record reference or id, e.g. PROD-12 or PROD-12-1
+        record_id: String,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature or requirement
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Set integration fields of a feature or requirement, e.g. the linked issue's key
+    Set {
+        /// Record reference or id, e.g. PROD-12 or PROD-12-1
+        record_id: String,
+
+        /// The integration the fields belong to
+        #[structopt(short, long = "integration")]
+        integration_id: String,
+
+        /// Integration field to set, e.g. --field key=JIRA-123; repeat for more fields
+        #[structopt(
+            long = "field",
+            required = true,
+            number_of_values = 1,
+            parse(try_from_str = parse_field)
+        )]
+        fields: Vec<(String, String)>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature or requirement
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Field {
+    /// List the custom field definitions of a product
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Set custom fields on a feature, epic, idea or release
+    Set {
+        /// Record reference or id, e.g. PROD-12, PROD-E-4, PROD-I-3 or PROD-R-5
+        record_id: String,
+
+        /// Custom field to set, e.g. --field score=8; repeat for more fields.
+        /// Multi-select and user fields take comma separated values
+        #[structopt(
+            long = "field",
+            required = true,
+            number_of_values = 1,
+            parse(try_from_str = parse_field)
+        )]
+        fields: Vec<(String, String)>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, epic, idea or release
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Workflow {
+    /// List a product's workflows with their statuses in order
+    List {
+        /// Product name or id, defaults to the profile's product
+        #[structopt(short, long)]
+        product_id: Option<String>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum User {
+    /// List the users of the account, or of a product with their roles
+    List {
+        /// Product name or id, lists the account's users when left out
+        #[structopt(short, long)]
+        product_id: Option<String>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a user by email or id
+    Get {
+        /// User email or id
+        #[structopt(short, long)]
+        user_id: String,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Todo {
+    /// List the to-dos assigned to you, or those attached to a record
+    List {
+        /// Record reference or id, e.g. PROD-12, lists your own to-dos when left out
+        record_id: Option<String>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic or release
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+
+        #[structopt(flatten)]
+        pages: PageArgs,
+    },
+    /// Get a to-do by id
+    Get {
+        /// To-do id
+        #[structopt(short, long)]
+        todo_id: String,
+    },
+    /// Create a to-do on a record
+    Create {
+        /// Record reference or id, e.g. PROD-12
+        record_id: String,
+
+        /// To-do name
+        #[structopt(short, long)]
+        name: String,
+
+        /// To-do description, html is allowed (optional)
+        #[structopt(long)]
+        body: Option<String>,
+
+        /// Comma separated emails or ids of the users to assign (optional)
+        #[structopt(short, long, use_delimiter = true)]
+        assignees: Vec<String>,
+
+        /// Due date, format: YYYY-MM-DD (optional)
+        #[structopt(short, long)]
+        due_date: Option<String>,
+
+        /// Record type, needed when it can't be told from the reference:
+        /// feature, requirement, idea, epic or release
+        #[structopt(long = "type")]
+        kind: Option<RecordKind>,
+    },
+    /// Mark to-dos complete
+    Complete {
+        /// To-do ids
+        #[structopt(required = true)]
+        todo_ids: Vec<String>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+enum Config {
+    /// List the profiles in the config file
+    List,
+    /// Add a profile, replacing any existing profile with the same name
+    Add {
+        /// Profile name
+        name: String,
+
+        /// The aha subdomain for this profile: <subdomain>.aha.io
+        #[structopt(short, long)]
+        subdomain: String,
+
+        /// Read the API token from this environment variable
+        #[structopt(long, conflicts_with_all = &["token-command", "token"])]
+        token_env: Option<String>,
+
+        /// Read the API token from the output of this shell command
+        #[structopt(long, conflicts_with = "token")]
+        token_command: Option<String>,
+
+        /// Store the API token in the config file
+        #[structopt(long)]
+        token: Option<String>,
+
+        /// Base url when not using https://<subdomain>.aha.io (optional)
+        #[structopt(long)]
+        base_url: Option<String>,
+
+        /// Default output format for this profile (optional)
+        #[structopt(short, long)]
+        format: Option<String>,
+
+        /// Default product name or id for this profile (optional)
+        #[structopt(short, long)]
+        product: Option<String>,
+
+        /// Make this the default profile
+        #[structopt(long)]
+        default: bool,
+    },
+    /// Remove a profile
+    Remove {
+        /// Profile name
+        name: String,
+    },
+}
+
+#[async_std::main]
+async fn main() {
+    let args = Cli::from_args();
+    if let Err(err) = run(&args).await {
+        eprintln!("blimey: {}", err);
+        std::process::exit(exit_code(&err));
+    }
+}
+
+/// Exit codes are distinct per error kind so scripts can tell a bad argument
+/// from an aha.io rejection or a network problem.
+fn exit_code(err: &Error) -> i32 {
+    match err {
+        Error::Validation(_) => 2,
+        Error::Http { .. } => 3,
+        Error::Transport(_) => 4,
+        Error::Serialization(_) => 5,
+        Error::Config(_) => 6,
+        Error::Io { .. } => 7,
+    }
+}
+
+/// What every api command needs, resolved from the command line, the
+/// environment and the selected profile, in that order.
+struct Context {
+    aha_request: AhaRequest,
+    output: Output,
+    product: Option<String>,
+}
+
+impl Context {
+    /// Builds the context from a single source of credentials: the profile
+    /// when there is one, otherwise --subdomain and --token together.
+    fn new(args: &Cli, profile: Option<&Profile>) -> Result<Self> {
+        let (subdomain, token, base_url) = match (profile, &args.subdomain, &args.token) {
+            (Some(profile), _, _) => {
+                profile.check_base_url()?;
+                let source = profile.token.as_ref().ok_or_else(|| {
+                    Error::Config(format!(
+                        "the profile for {} has no token, add one with blimey config add",
+                        profile.subdomain
+                    ))
+                })?;
+                (
+                    profile.subdomain.clone(),
+                    source.resolve()?,
+                    profile.base_url.clone(),
+                )
+            }
+            (None, Some(subdomain), Some(token)) => (subdomain.clone(), token.clone(), None),
+            (None, None, None) => return Err(Error::Validation(
+                "no account given: pass --subdomain and --token, set BLIMEY_AHA_SUBDOMAIN and BLIMEY_AHA_TOKEN, or add a profile"
+                    .into(),
+            )),
+            (None, _, _) => return Err(Error::Validation(
+                "--subdomain and --token (or BLIMEY_AHA_SUBDOMAIN and BLIMEY_AHA_TOKEN) must be given together, or pass --profile"
+                    .into(),
+            )),
+        };
+        let aha_request = match base_url {
+            Some(base_url) => AhaRequest::with_url(&token, &subdomain, &base_url),
+            None => AhaRequest::new(&token, &subdomain),
+        }
+        .with_retry_policy(RetryPolicy {
+            max_retries: args.max_retries,
+            ..RetryPolicy::default()
+        });
+        let format = args
+            .format
+            .as_deref()
+            .or_else(|| profile.and_then(|p| p.format.as_deref()))
+            .unwrap_or("json");
+        let output = Output {
+            format: format.parse::<Format>()?,
+            columns: args.columns.clone(),
+            // only truncate when a person is looking, never when piped
+            width: terminal_size_of(std::io::stdout()).map(|(Width(w), _)| w as usize),
+        };
+        Ok(Self {
+            aha_request,
+            output,
+            product: profile.and_then(|p| p.product.clone()),
+        })
+    }
+
+    fn product_id<'a>(&'a self, product_id: &'a Option<String>) -> Result<&'a str> {
+        product_id
+            .as_deref()
+            .or(self.product.as_deref())
+            .ok_or_else(|| {
+                Error::Validation(
+                    "no product given: pass --product-id or set a product on the profile".into(),
+                )
+            })
+    }
+}
+
+fn load_config() -> Result<(config::Config, PathBuf)> {
+    let config_path = config::Config::default_path()
+        .ok_or_else(|| Error::Config("could not find a config directory".into()))?;
+    Ok((config::Config::load(&config_path)?, config_path))
+}
+
+async fn run(args: &Cli) -> Result<()> {
+    if let Some(Aha::Config {
+        commands: Some(cmd),
+    }) = &args.subcommands
+    {
+        let (config, config_path) = load_config()?;
+        return run_config(config, &config_path, cmd);
+    }
+
+    // the config file is only read when a profile is needed, so explicit
+    // credentials work without one
+    let profile = if args.profile.is_some() || (args.subdomain.is_none() && args.token.is_none()) {
+        let (config, _) = load_config()?;
+        config.profile(args.profile.as_deref())?.cloned()
+    } else {
+        None
+    };
+    let ctx = Context::new(args, profile.as_ref())?;
+    if let Some(scmd) = &args.subcommands {
+        match scmd {
+            Aha::Product {
+                commands: Some(cmd),
+            } => run_product(&ctx, cmd).await?,
+            Aha::Release {
+                commands: Some(cmd),
+            } => run_release(&ctx, cmd).await?,
+            Aha::ReleasePhase {
+                commands: Some(cmd),
+            } => run_release_phase(&ctx, cmd).await?,
+            Aha::Persona {
+                commands: Some(cmd),
+            } => run_persona(&ctx, cmd).await?,
+            Aha::Competitor {
+                commands: Some(cmd),
+            } => run_competitor(&ctx, cmd).await?,
+            Aha::Initiative {
+                commands: Some(cmd),
+            } => run_initiative(&ctx, cmd).await?,
+            Aha::Goal {
+                commands: Some(cmd),
+            } => run_goal(&ctx, cmd).await?,
+            Aha::Feature {
+                commands: Some(cmd),
+            } => run_feature(&ctx, cmd).await?,
+            Aha::Epic {
+                commands: Some(cmd),
+            } => run_epic(&ctx, cmd).await?,
+            Aha::Idea {
+                commands: Some(cmd),
+            } => run_idea(&ctx, cmd).await?,
+            Aha::Requirement {
+                commands: Some(cmd),
+            } => run_requirement(&ctx, cmd).await?,
+            Aha::Comment {
+                commands: Some(cmd),
+            } => run_comment(&ctx, cmd).await?,
+            Aha::Tag {
+                commands: Some(cmd),
+            } => run_tag(&ctx, cmd).await?,
+            Aha::Attachment {
+                commands: Some(cmd),
+            } => run_attachment(&ctx, cmd).await?,
+            Aha::Time {
+                commands: Some(cmd),
+            } => run_time(&ctx, cmd).await?,
+            Aha::Link {
+                commands: Some(cmd),
+            } => run_link(&ctx, cmd).await?,
+            Aha::Integration {
+                commands: Some(cmd),
+            } => run_integration(&ctx, cmd).await?,
+            Aha::Field {
+                commands: Some(cmd),
+            } => run_field(&ctx, cmd).await?,
+            Aha::Workflow {
+                commands: Some(cmd),
+            } => run_workflow(&ctx, cmd).await?,
+            Aha::User {
+                commands: Some(cmd),
+            } => run_user(&ctx, cmd).await?,
+            Aha::Todo {
+                commands: Some(cmd),
+            } => run_todo(&ctx, cmd).await?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Asks on the terminal before deleting `what`. `--yes` skips the question,
+/// and without a terminal to ask on nothing is deleted.
+fn confirm_delete(what: &str, yes: bool) -> Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(Error::Validation(format!(
+            "refusing to delete {} without confirmation, pass --yes",
+            what
+        )));
+    }
+    eprint!("Delete {}? [y/N] ", what);
+    let mut answer = String::new();
+    io::stderr()
+        .flush()
+        .and_then(|_| io::stdin().read_line(&mut answer))
+        .map_err(|err| Error::Validation(format!("could not read confirmation: {}", err)))?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn describe(kind: &str, reference_num: &Option<String>, id: &str, name: &str) -> String {
+    format!(
+        "{} {} \"{}\"",
+        kind,
+        reference_num.as_deref().unwrap_or(id),
+        name
+    )
+}
+
+fn print_deleted(what: &str, deleted: bool) {
+    if deleted {
+        println!("Deleted {}", what);
+    } else {
+        eprintln!("Nothing deleted");
+    }
+}
+
+fn print_record<T: Record>(output: &Output, record: &T) -> Result<()> {
+    print!("{}", output.record(record)?);
+    Ok(())
+}
+
+/// Prints records belonging to the one just printed, e.g. its custom
+/// fields, as a second table. Other formats already include them.
+fn print_details<T: Record>(output: &Output, records: &[T]) -> Result<()> {
+    if output.format == Format::Table && !records.is_empty() {
+        println!();
+        print!("{}", output.details(records)?);
+    }
+    Ok(())
+}
+
+/// Prints a created or updated record. aha.io may accept a change without
+/// sending the record back, in which case there is nothing to print.
+fn print_saved<T: Record>(output: &Output, record: &Option<T>) -> Result<()> {
+    match record {
+        Some(record) => print_record(output, record),
+        None => {
+            eprintln!("Saved, aha.io sent no record back");
+            Ok(())
+        }
+    }
+}
+
+fn print_list<L>(output: &Output, list: &L) -> Result<()>
+where
+    L: Paginated + Serialize,
+    L::Record: Record,
+{
+    print!("{}", output.list(list)?);
+    Ok(())
+}
+
+async fn run_product(ctx: &Context, cmd: &Product) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Product::List {
+            updated_since,
+            pages,
+        } => print_list(
+            output,
+            &aha_request
+                .list_products(updated_since, &pages.paging())
+                .await?,
+        ),
+        Product::Get { product_id } => print_record(
+            output,
+            &aha_request.get_product(ctx.product_id(product_id)?).await?,
+        ),
+        Product::Create {
+            name,
+            prefix,
+            parent_id,
+            workspace_type,
+        } => print_saved(
+            output,
+            &aha_request
+                .create_product(name, prefix, parent_id, workspace_type)
+                .await?,
+        ),
+        Product::Update {
+            product_id,
+            name,
+            prefix,
+            parent_id,
+        } => print_saved(
+            output,
+            &aha_request
+                .update_product(ctx.product_id(product_id)?, name, prefix, parent_id)
+                .await?,
+        ),
+    }
+}
+
+async fn run_release(ctx: &Context, cmd: &Release) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Release::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_releases_for_product(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Release::Get { release_id } => {
+            let mut release = aha_request.get_release_with_phases(release_id).await?;
+            release.record_links = aha_request
+                .list_record_links(RecordKind::Release, &release.id)
+                .await?;
+            print_record(output, &release)?;
+            print_details(output, &release.release_phases)?;
+            print_details(output, &release.record_links)?;
+            print_details(output, &release.custom_fields)
+        }
+        Release::Create { product_id, name } => print_saved(
+            output,
+            &aha_request
+                .create_release_for_product(ctx.product_id(product_id)?, name)
+                .await?,
+        ),
+        Release::Delete { release_id, yes } => {
+            let release = aha_request.get_release(release_id).await?;
+            let what = describe(
+                "release",
+                &release.reference_num,
+                &release.id,
+                &release.name,
+            );
+            let deleted = confirm_delete(&what, *yes)?;
+            if deleted {
+                aha_request.delete_release(&release.id).await?;
+            }
+            print_deleted(&what, deleted);
+            Ok(())
+        }
+        Release::Update {
+            product_id,
+            release_id,
+            name,
+            parent_id,
+        } => print_saved(
+            output,
+            &aha_request
+                .update_release_for_product(
+                    ctx.product_id(product_id)?,
+                    release_id,
+                    name,
+                    parent_id,
+                )
+                .await?,
+        ),
+    }
+}
+
+async fn run_release_phase(ctx: &Context, cmd: &ReleasePhase) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        ReleasePhase::List { release_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_release_phases(release_id, &pages.paging())
+                .await?,
+        ),
+        ReleasePhase::Get { phase_id } => {
+            print_record(output, &aha_request.get_release_phase(phase_id).await?)
+        }
+        ReleasePhase::Create {
+            release_id,
+            name,
+            start_on,
+            end_on,
+            milestone,
+            description,
+        } => {
+            let phase = NewReleasePhase {
+                name: name.clone(),
+                phase_type: if *milestone { "milestone" } else { "phase" }.into(),
+                start_on: start_on.clone(),
+                end_on: end_on
+                    .clone()
+                    .or_else(|| milestone.then(|| start_on.clone())),
+                description: description.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request.create_release_phase(release_id, &phase).await?,
+            )
+        }
+        ReleasePhase::Update {
+            phase_id,
+            name,
+            start_on,
+            end_on,
+            description,
+        } => {
+            let phase = ReleasePhaseUpdate {
+                name: name.clone(),
+                start_on: start_on.clone(),
+                end_on: end_on.clone(),
+                description: description.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request.update_release_phase(phase_id, &phase).await?,
+            )
+        }
+        ReleasePhase::Delete { phase_id, yes } => {
+            let phase = aha_request.get_release_phase(phase_id).await?;
+            let what = describe("release phase", &None, &phase.id, &phase.name);
+            let deleted = confirm_delete(&what, *yes)?;
+            if deleted {
+                aha_request.delete_release_phase(&phase.id).await?;
+            }
+            print_deleted(&what, deleted);
+            Ok(())
+        }
+    }
+}
+
+async fn run_persona(ctx: &Context, cmd: &Persona) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Persona::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_personas_for_product(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Persona::Get { persona_id } => {
+            let persona = aha_request.get_persona(persona_id).await?;
+            print_record(output, &persona)?;
+            print_details(output, &persona.custom_fields)
+        }
+        Persona::Create {
+            product_id,
+            name,
+            description,
+            fields,
+        } => {
+            let product_id = ctx.product_id(product_id)?;
+            let mut persona = NewResearchRecord {
+                name: name.clone(),
+                description: description.clone(),
+                ..Default::default()
+            };
+            if !fields.is_empty() {
+                persona.custom_fields = custom_field_values(ctx, Some(product_id), fields).await?;
+            }
+            print_saved(
+                output,
+                &aha_request
+                    .create_persona_for_product(product_id, &persona)
+                    .await?,
+            )
+        }
+        Persona::Update {
+            persona_id,
+            name,
+            description,
+            fields,
+        } => {
+            let mut update = ResearchRecordUpdate {
+                name: name.clone(),
+                description: description.clone(),
+                ..Default::default()
+            };
+            if !fields.is_empty() {
+                // custom fields are defined per product, so look the persona up first
+                let current = aha_request.get_persona(persona_id).await?;
+                update.custom_fields =
+                    custom_field_values(ctx, current.product_id.as_deref(), fields).await?;
+            }
+            print_saved(
+                output,
+                &aha_request.update_persona(persona_id, &update).await?,
+            )
+        }
+    }
+}
+
+async fn run_competitor(ctx: &Context, cmd: &Competitor) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Competitor::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_competitors_for_product(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Competitor::Get { competitor_id } => {
+            let competitor = aha_request.get_competitor(competitor_id).await?;
+            print_record(output, &competitor)?;
+            print_details(output, &competitor.custom_fields)
+        }
+        Competitor::Create {
+            product_id,
+            name,
+            description,
+            fields,
+        } => {
+            let product_id = ctx.product_id(product_id)?;
+            let mut competitor = NewResearchRecord {
+                name: name.clone(),
+                description: description.clone(),
+                ..Default::default()
+            };
+            if !fields.is_empty() {
+                competitor.custom_fields =
+                    custom_field_values(ctx, Some(product_id), fields).await?;
+            }
+            print_saved(
+                output,
+                &aha_request
+                    .create_competitor_for_product(product_id, &competitor)
+                    .await?,
+            )
+        }
+        Competitor::Update {
+            competitor_id,
+            name,
+            description,
+            fields,
+        } => {
+            let mut update = ResearchRecordUpdate {
+                name: name.clone(),
+                description: description.clone(),
+                ..Default::default()
+            };
+            if !fields.is_empty() {
+                // custom fields are defined per product, so look the competitor up first
+                let current = aha_request.get_competitor(competitor_id).await?;
+                update.custom_fields =
+                    custom_field_values(ctx, current.product_id.as_deref(), fields).await?;
+            }
+            print_saved(
+                output,
+                &aha_request
+                    .update_competitor(competitor_id, &update)
+                    .await?,
+            )
+        }
+    }
+}
+
+async fn run_initiative(ctx: &Context, cmd: &Initiative) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Initiative::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_initiatives_for_product(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Initiative::Get { initiative_id } => {
+            print_record(output, &aha_request.get_initiative(initiative_id).await?)
+        }
+        Initiative::Create {
+            product_id,
+            name,
+            description,
+            status,
+        } => {
+            let initiative = NewStrategy {
+                name: name.clone(),
+                description: description.clone(),
+                workflow_status: status.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .create_initiative_for_product(ctx.product_id(product_id)?, &initiative)
+                    .await?,
+            )
+        }
+        Initiative::Update {
+            initiative_id,
+            name,
+            status,
+            progress,
+        } => {
+            let initiative = StrategyUpdate {
+                name: name.clone(),
+                workflow_status: status.clone(),
+                progress: *progress,
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .update_initiative(initiative_id, &initiative)
+                    .await?,
+            )
+        }
+        Initiative::Link {
+            initiative_id,
+            feature,
+            epic,
+        } => match (feature, epic) {
+            (Some(feature_id), _) => print_saved(
+                output,
+                &aha_request
+                    .link_feature_to_initiative(feature_id, initiative_id)
+                    .await?,
+            ),
+            (None, Some(epic_id)) => print_saved(
+                output,
+                &aha_request
+                    .link_epic_to_initiative(epic_id, initiative_id)
+                    .await?,
+            ),
+            (None, None) => Err(Error::Validation(
+                "nothing to link: pass --feature or --epic".into(),
+            )),
+        },
+    }
+}
+
+async fn run_goal(ctx: &Context, cmd: &Goal) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Goal::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_goals_for_product(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Goal::Get { goal_id } => print_record(output, &aha_request.get_goal(goal_id).await?),
+        Goal::Create {
+            product_id,
+            name,
+            description,
+            status,
+        } => {
+            let goal = NewStrategy {
+                name: name.clone(),
+                description: description.clone(),
+                workflow_status: status.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .create_goal_for_product(ctx.product_id(product_id)?, &goal)
+                    .await?,
+            )
+        }
+        Goal::Update {
+            goal_id,
+            name,
+            status,
+            progress,
+        } => {
+            let goal = StrategyUpdate {
+                name: name.clone(),
+                workflow_status: status.clone(),
+                progress: *progress,
+            };
+            print_saved(output, &aha_request.update_goal(goal_id, &goal).await?)
+        }
+        Goal::Link {
+            goal_id,
+            feature,
+            epic,
+        } => match (feature, epic) {
+            (Some(feature_id), _) => print_saved(
+                output,
+                &aha_request
+                    .link_feature_to_goal(feature_id, goal_id)
+                    .await?,
+            ),
+            (None, Some(epic_id)) => print_saved(
+                output,
+                &aha_request.link_epic_to_goal(epic_id, goal_id).await?,
+            ),
+            (None, None) => Err(Error::Validation(
+                "nothing to link: pass --feature or --epic".into(),
+            )),
+        },
+    }
+}
+
+async fn run_feature(ctx: &Context, cmd: &Feature) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Feature::List {
+            product_id,
+            tag,
+            assignee,
+            updated_since,
+            pages,
+        } => {
+            let filters = FeatureFilters {
+                tag: tag.clone(),
+                assigned_to_user: assignee_id(aha_request, assignee).await?,
+                updated_since: updated_since.clone(),
+            };
+            print_list(
+                output,
+                &aha_request
+                    .list_features_for_product(
+                        ctx.product_id(product_id)?,
+                        &filters,
+                        &pages.paging(),
+                    )
+                    .await?,
+            )
+        }
+        Feature::Get {
+            feature_id,
+            requirements,
+        } => {
+            let mut feature = aha_request.get_feature(feature_id).await?;
+            // the embedded list may be incomplete, fetch every page instead
+            feature.requirements = None;
+            if *requirements {
+                let list = aha_request
+                    .list_requirements_for_feature(&feature.id, &Paging::all())
+                    .await?;
+                feature.requirements = Some(list.requirements);
+            }
+            feature.record_links = aha_request
+                .list_record_links(RecordKind::Feature, &feature.id)
+                .await?;
+            print_record(output, &feature)?;
+            print_details(
+                output,
+                &LinkedIssue::from_fields(&feature.integration_fields),
+            )?;
+            print_details(output, &feature.record_links)?;
+            print_details(output, feature.requirements.as_deref().unwrap_or_default())?;
+            print_details(output, &feature.custom_fields)
+        }
+        Feature::Find { key, product_id } => {
+            let feature = aha_request
+                .find_feature_by_integration_key(ctx.product_id(product_id)?, key)
+                .await?;
+            print_record(output, &feature)?;
+            print_details(
+                output,
+                &LinkedIssue::from_fields(&feature.integration_fields),
+            )?;
+            print_details(output, &feature.custom_fields)
+        }
+        Feature::Create {
+            release_id,
+            name,
+            description,
+            status,
+            assignee,
+            tags,
+            start_date,
+            due_date,
+            initial_estimate,
+        } => {
+            let feature = NewFeature {
+                name: name.clone(),
+                description: description.clone(),
+                workflow_status: status.clone(),
+                assigned_to_user: assignee_id(aha_request, assignee).await?,
+                tags: tags.clone(),
+                start_date: start_date.clone(),
+                due_date: due_date.clone(),
+                initial_estimate: initial_estimate.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request.create_feature(release_id, &feature).await?,
+            )
+        }
+        Feature::Delete { feature_id, yes } => {
+            let feature = aha_request.get_feature(feature_id).await?;
+            let what = describe(
+                "feature",
+                &feature.reference_num,
+                &feature.id,
+                &feature.name,
+            );
+            let deleted = confirm_delete(&what, *yes)?;
+            if deleted {
+                aha_request.delete_feature(&feature.id).await?;
+            }
+            print_deleted(&what, deleted);
+            Ok(())
+        }
+        Feature::Update {
+            feature_id,
+            name,
+            start_date,
+            due_date,
+            status,
+            original_estimate,
+            remaining_estimate,
+            work_done,
+            fields,
+        } => {
+            let mut update = FeatureUpdate {
+                name: name.clone(),
+                start_date: start_date.clone(),
+                due_date: due_date.clone(),
+                original_estimate: original_estimate.clone(),
+                remaining_estimate: remaining_estimate.clone(),
+                work_done: work_done.clone(),
+                ..Default::default()
+            };
+            if status.is_some() || !fields.is_empty() {
+                // statuses and custom fields depend on the feature's product
+                let current = aha_request.get_feature(feature_id).await?;
+                if let Some(status) = status {
+                    let status = aha_request.resolve_feature_status(&current, status).await?;
+                    update.workflow_status = Some(status.id);
+                }
+                if !fields.is_empty() {
+                    update.custom_fields =
+                        custom_field_values(ctx, current.product_id.as_deref(), fields).await?;
+                }
+            }
+            // one request, so a rejected field leaves the feature as it was
+            let feature = match aha_request.update_feature(feature_id, &update).await? {
+                Some(feature) => feature,
+                None => aha_request.get_feature(feature_id).await?,
+            };
+            print_record(output, &feature)?;
+            print_details(output, &feature.custom_fields)
+        }
+    }
+}
+
+async fn run_epic(ctx: &Context, cmd: &Epic) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Epic::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_epics_for_product(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Epic::Get { epic_id } => {
+            let mut epic = aha_request.get_epic_with_rollup(epic_id).await?;
+            epic.epic.record_links = aha_request
+                .list_record_links(RecordKind::Epic, &epic.epic.id)
+                .await?;
+            print_record(output, &epic)?;
+            print_details(output, &epic.epic.record_links)?;
+            print_details(output, &epic.epic.custom_fields)
+        }
+        Epic::Create {
+            release_id,
+            name,
+            description,
+            status,
+            assignee,
+            tags,
+            start_date,
+            due_date,
+        } => {
+            let epic = NewEpic {
+                name: name.clone(),
+                description: description.clone(),
+                workflow_status: status.clone(),
+                assigned_to_user: assignee_id(aha_request, assignee).await?,
+                tags: tags.clone(),
+                start_date: start_date.clone(),
+                due_date: due_date.clone(),
+            };
+            print_saved(output, &aha_request.create_epic(release_id, &epic).await?)
+        }
+        Epic::Update {
+            epic_id,
+            name,
+            description,
+            status,
+            assignee,
+            start_date,
+            due_date,
+        } => {
+            let epic = EpicUpdate {
+                name: name.clone(),
+                description: description.clone(),
+                workflow_status: status.clone(),
+                assigned_to_user: assignee_id(aha_request, assignee).await?,
+                start_date: start_date.clone(),
+                due_date: due_date.clone(),
+            };
+            print_saved(output, &aha_request.update_epic(epic_id, &epic).await?)
+        }
+        Epic::Features { epic_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_features_for_epic(epic_id, &pages.paging())
+                .await?,
+        ),
+    }
+}
+
+async fn run_idea(ctx: &Context, cmd: &Idea) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Idea::List {
+            product_id,
+            status,
+            category,
+            tag,
+            updated_since,
+            pages,
+        } => {
+            let filters = IdeaFilters {
+                workflow_status: status.clone(),
+                category: category.clone(),
+                tag: tag.clone(),
+                updated_since: updated_since.clone(),
+            };
+            print_list(
+                output,
+                &aha_request
+                    .list_ideas_for_product(ctx.product_id(product_id)?, &filters, &pages.paging())
+                    .await?,
+            )
+        }
+        Idea::Get { idea_id } => {
+            let mut idea = aha_request.get_idea(idea_id).await?;
+            idea.record_links = aha_request
+                .list_record_links(RecordKind::Idea, &idea.id)
+                .await?;
+            print_record(output, &idea)?;
+            print_details(output, &idea.record_links)?;
+            print_details(output, &idea.custom_fields)
+        }
+        Idea::Create {
+            product_id,
+            name,
+            description,
+            categories,
+            tags,
+            status,
+        } => {
+            let idea = NewIdea {
+                name: name.clone(),
+                description: description.clone(),
+                categories: categories.clone(),
+                tags: tags.clone(),
+                workflow_status: status.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .create_idea(ctx.product_id(product_id)?, &idea)
+                    .await?,
+            )
+        }
+        Idea::Vote {
+            idea_id,
+            email,
+            organization,
+            value,
+            description,
+        } => {
+            let endorsement = NewEndorsement {
+                email: email.clone(),
+                organization: organization.clone(),
+                value: *value,
+                description: description.clone(),
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .create_idea_endorsement(idea_id, &endorsement)
+                    .await?,
+            )
+        }
+        Idea::Comments { idea_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_comments_for_idea(idea_id, &pages.paging())
+                .await?,
+        ),
+    }
+}
+
+async fn run_requirement(ctx: &Context, cmd: &Requirement) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Requirement::List { feature_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_requirements_for_feature(feature_id, &pages.paging())
+                .await?,
+        ),
+        Requirement::Get { requirement_id } => {
+            let mut requirement = aha_request.get_requirement(requirement_id).await?;
+            requirement.record_links = aha_request
+                .list_record_links(RecordKind::Requirement, &requirement.id)
+                .await?;
+            print_record(output, &requirement)?;
+            print_details(output, &requirement.record_links)
+        }
+        Requirement::Create {
+            feature_id,
+            name,
+            description,
+            status,
+            assignee,
+        } => {
+            let requirement = NewRequirement {
+                name: name.clone(),
+                description: description.clone(),
+                workflow_status: status.clone(),
+                assigned_to_user: assignee_id(aha_request, assignee).await?,
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .create_requirement(feature_id, &requirement)
+                    .await?,
+            )
+        }
+        Requirement::Update {
+            requirement_id,
+            name,
+            status,
+            assignee,
+        } => {
+            let requirement = RequirementUpdate {
+                name: name.clone(),
+                workflow_status: status.clone(),
+                assigned_to_user: assignee_id(aha_request, assignee).await?,
+                ..RequirementUpdate::default()
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .update_requirement(requirement_id, &requirement)
+                    .await?,
+            )
+        }
+        Requirement::Reorder { requirement_ids } => {
+            let requirements = aha_request.reorder_requirements(requirement_ids).await?;
+            print!("{}", output.records(&requirements)?);
+            Ok(())
+        }
+        Requirement::Delete {
+            requirement_id,
+            yes,
+        } => {
+            let requirement = aha_request.get_requirement(requirement_id).await?;
+            let what = describe(
+                "requirement",
+                &requirement.reference_num,
+                &requirement.id,
+                &requirement.name,
+            );
+            let deleted = confirm_delete(&what, *yes)?;
+            if deleted {
+                aha_request.delete_requirement(&requirement.id).await?;
+            }
+            print_deleted(&what, deleted);
+            Ok(())
+        }
+    }
+}
+
+async fn run_comment(ctx: &Context, cmd: &Comment) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Comment::List {
+            record_id,
+            kind,
+            pages,
+        } => print_list(
+            output,
+            &aha_request
+                .list_comments(record_kind(record_id, kind)?, record_id, &pages.paging())
+                .await?,
+        ),
+        Comment::Add {
+            record_id,
+            body,
+            file,
+            kind,
+            html,
+        } => {
+            let kind = record_kind(record_id, kind)?;
+            let body = read_body(body, file)?;
+            let body = if *html { body } else { markdown_to_html(&body) };
+            print_saved(
+                output,
+                &aha_request.create_comment(kind, record_id, &body).await?,
+            )
+        }
+    }
+}
+
+/// The tags of one record, as printed by `blimey tag`.
+#[derive(Serialize)]
+struct RecordTags<'a> {
+    record_id: &'a str,
+    tags: Vec<String>,
+}
+
+impl Record for RecordTags<'_> {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["record_id", "tags"];
+}
+
+async fn run_tag(ctx: &Context, cmd: &Tag) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    let (record_id, tags) = match cmd {
+        Tag::List { record_id, kind } => (
+            record_id,
+            aha_request
+                .get_tags(record_kind(record_id, kind)?, record_id)
+                .await?,
+        ),
+        Tag::Add {
+            record_id,
+            tags,
+            kind,
+        } => (
+            record_id,
+            aha_request
+                .add_tags(record_kind(record_id, kind)?, record_id, tags)
+                .await?,
+        ),
+        Tag::Set {
+            record_id,
+            tags,
+            kind,
+        } => (
+            record_id,
+            aha_request
+                .set_tags(record_kind(record_id, kind)?, record_id, tags)
+                .await?,
+        ),
+        Tag::Remove {
+            record_id,
+            tags,
+            kind,
+        } => (
+            record_id,
+            aha_request
+                .remove_tags(record_kind(record_id, kind)?, record_id, tags)
+                .await?,
+        ),
+    };
+    print_record(output, &RecordTags { record_id, tags })
+}
+
+async fn run_time(ctx: &Context, cmd: &Time) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Time::List {
+            record_id,
+            kind,
+            pages,
+        } => print_list(
+            output,
+            &aha_request
+                .list_time_tracking_events(
+                    record_kind(record_id, kind)?,
+                    record_id,
+                    &pages.paging(),
+                )
+                .await?,
+        ),
+        Time::Log {
+            record_id,
+            amount,
+            description,
+            date,
+            kind,
+        } => {
+            let event = NewTimeTrackingEvent {
+                work_date: date.clone(),
+                description: description.clone(),
+                ..NewTimeTrackingEvent::new(*amount)
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .log_time(record_kind(record_id, kind)?, record_id, &event)
+                    .await?,
+            )
+        }
+    }
+}
+
+async fn run_link(ctx: &Context, cmd: &Link) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Link::List { record_id, kind } => {
+            let links = aha_request
+                .list_record_links(record_kind(record_id, kind)?, record_id)
+                .await?;
+            print!("{}", output.records(&links)?);
+            Ok(())
+        }
+        Link::Create {
+            record_id,
+            link_type,
+            other_id,
+            kind,
+            other_kind,
+        } => print_saved(
+            output,
+            &aha_request
+                .create_record_link(
+                    record_kind(record_id, kind)?,
+                    record_id,
+                    *link_type,
+                    record_kind(other_id, other_kind)?,
+                    other_id,
+                )
+                .await?,
+        ),
+        Link::Delete { link_id, yes } => {
+            let link = aha_request.get_record_link(link_id).await?;
+            let reference = |record: &Option<LinkedRecord>| {
+                record
+                    .as_ref()
+                    .map(|r| r.reference_num.clone().unwrap_or_else(|| r.id.clone()))
+                    .unwrap_or_default()
+            };
+            let what = format!(
+                "link {} \"{} {} {}\"",
+                link.id,
+                reference(&link.parent_record),
+                link.link_type
+                    .as_deref()
+                    .unwrap_or("linked to")
+                    .to_lowercase(),
+                reference(&link.child_record)
+            );
+            let deleted = confirm_delete(&what, *yes)?;
+            if deleted {
+                aha_request.delete_record_link(&link.id).await?;
+            }
+            print_deleted(&what, deleted);
+            Ok(())
+        }
+    }
+}
+
+async fn run_integration(ctx: &Context, cmd: &Integration) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    let fields = match cmd {
+        Integration::List { record_id, kind } => {
+            aha_request
+                .list_integration_fields(record_kind(record_id, kind)?, record_id)
+                .await?
+        }
+        Integration::Set {
+            record_id,
+            integration_id,
+            fields,
+            kind,
+        } => {
+            aha_request
+                .set_integration_fields(
+                    record_kind(record_id, kind)?,
+                    record_id,
+                    integration_id,
+                    fields,
+                )
+                .await?
+        }
+    };
+    print!("{}", output.records(&fields)?);
+    Ok(())
+}
+
+/// A file saved by `blimey attachment download`.
+#[derive(Serialize)]
+struct DownloadedFile {
+    file_name: String,
+    path: String,
+    file_size: usize,
+    content_type: Option<String>,
+}
+
+impl Record for DownloadedFile {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["file_name", "file_size", "content_type", "path"];
+}
+
+async fn run_attachment(ctx: &Context, cmd: &Attachment) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Attachment::List { record_id, kind } => {
+            let attachments = aha_request
+                .list_attachments(record_kind(record_id, kind)?, record_id)
+                .await?;
+            print!("{}", output.records(&attachments)?);
+            Ok(())
+        }
+        Attachment::Upload {
+            record_id,
+            files,
+            kind,
+        } => {
+            let kind = record_kind(record_id, kind)?;
+            let mut attachments = Vec::with_capacity(files.len());
+            for file in files {
+                let content = std::fs::read(file).map_err(|err| {
+                    Error::Validation(format!("could not read {}: {}", file.display(), err))
+                })?;
+                let file_name = file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "attachment".into());
+                match aha_request
+                    .upload_attachment(kind, record_id, &file_name, &content)
+                    .await?
+                {
+                    Some(attachment) => attachments.push(attachment),
+                    None => eprintln!(
+                        "Uploaded {}, aha.io sent no attachment back",
+                        file.display()
+                    ),
+                }
+            }
+            print!("{}", output.records(&attachments)?);
+            Ok(())
+        }
+        Attachment::Download {
+            record_id,
+            output_dir,
+            kind,
+        } => {
+            let attachments = aha_request
+                .list_attachments(record_kind(record_id, kind)?, record_id)
+                .await?;
+            std::fs::create_dir_all(output_dir).map_err(|source| Error::Io {
+                path: output_dir.clone(),
+                source,
+            })?;
+            let mut downloaded: Vec<DownloadedFile> = Vec::with_capacity(attachments.len());
+            for attachment in &attachments {
+                // never trust a file name to stay inside the directory
+                let mut file_name = attachment
+                    .file_name
+                    .as_deref()
+                    .and_then(|name| Path::new(name).file_name())
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| attachment.id.clone());
+                if downloaded.iter().any(|d| d.file_name == file_name) {
+                    file_name = format!("{}-{}", attachment.id, file_name);
+                }
+                let content = aha_request.download_attachment(attachment).await?;
+                let path = output_dir.join(&file_name);
+                std::fs::write(&path, &content).map_err(|source| Error::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                downloaded.push(DownloadedFile {
+                    file_name,
+                    path: path.display().to_string(),
+                    file_size: content.len(),
+                    content_type: attachment.content_type.clone(),
+                });
+            }
+            print!("{}", output.records(&downloaded)?);
+            Ok(())
+        }
+    }
+}
+
+async fn run_field(ctx: &Context, cmd: &Field) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Field::List { product_id, pages } => print_list(
+            output,
+            &aha_request
+                .list_custom_field_definitions(ctx.product_id(product_id)?, &pages.paging())
+                .await?,
+        ),
+        Field::Set {
+            record_id,
+            fields,
+            kind,
+        } => {
+            let kind = record_kind(record_id, kind)?;
+            let product_id = match kind {
+                RecordKind::Feature => aha_request.get_feature(record_id).await?.product_id,
+                RecordKind::Epic => aha_request.get_epic(record_id).await?.product_id,
+                RecordKind::Idea => aha_request.get_idea(record_id).await?.product_id,
+                RecordKind::Release => aha_request.get_release(record_id).await?.product_id,
+                _ => None,
+            };
+            let values = custom_field_values(ctx, product_id.as_deref(), fields).await?;
+            let custom_fields = aha_request
+                .set_custom_fields(kind, record_id, &values)
+                .await?;
+            print!("{}", output.records(&custom_fields)?);
+            Ok(())
+        }
+    }
+}
+
+fn parse_field(field: &str) -> std::result::Result<(String, String), String> {
+    match field.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected key=value, got '{}'", field)),
+    }
+}
+
+/// Parses `--field key=value` arguments using the product's custom field
+/// definitions. Fields can be given by key or by name, and user fields by
+/// email.
+async fn custom_field_values(
+    ctx: &Context,
+    product_id: Option<&str>,
+    fields: &[(String, String)],
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let aha_request = &ctx.aha_request;
+    let product_id = product_id.map(String::from);
+    let definitions = aha_request
+        .list_custom_field_definitions(ctx.product_id(&product_id)?, &Paging::all())
+        .await?
+        .custom_field_definitions;
+    let mut values = serde_json::Map::new();
+    for (key, raw) in fields {
+        let definition = definitions
+            .iter()
+            .find(|d| d.key == *key || d.name.eq_ignore_ascii_case(key))
+            .ok_or_else(|| Error::Validation(format!("no custom field '{}'", key)))?;
+        let mut value = definition.parse_value(raw)?;
+        if definition.kind() == CustomFieldKind::Users {
+            let users: Vec<String> = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|user| !user.is_empty())
+                .map(String::from)
+                .collect();
+            value = aha_request.resolve_user_ids(&users).await?.into();
+        }
+        values.insert(definition.key.clone(), value);
+    }
+    Ok(values)
+}
+
+/// One status of a workflow, as printed by `blimey workflow list`.
+#[derive(Serialize)]
+struct WorkflowStatusRow<'a> {
+    workflow: &'a str,
+    position: Option<i64>,
+    id: &'a str,
+    name: &'a str,
+    complete: Option<bool>,
+}
+
+impl Record for WorkflowStatusRow<'_> {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["workflow", "position", "name", "id", "complete"];
+}
+
+async fn run_workflow(ctx: &Context, cmd: &Workflow) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Workflow::List { product_id } => {
+            let workflows = aha_request
+                .list_workflows_for_product(ctx.product_id(product_id)?)
+                .await?;
+            let rows: Vec<WorkflowStatusRow> = workflows
+                .iter()
+                .flat_map(|workflow| {
+                    workflow
+                        .workflow_statuses
+                        .iter()
+                        .map(move |status| WorkflowStatusRow {
+                            workflow: &workflow.name,
+                            position: status.position,
+                            id: &status.id,
+                            name: &status.name,
+                            complete: status.complete,
+                        })
+                })
+                .collect();
+            print!("{}", output.records(&rows)?);
+            Ok(())
+        }
+    }
+}
+
+async fn run_user(ctx: &Context, cmd: &User) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        User::List {
+            product_id: Some(product_id),
+            pages,
+        } => print_list(
+            output,
+            &aha_request
+                .list_users_for_product(product_id, &pages.paging())
+                .await?,
+        ),
+        User::List {
+            product_id: None,
+            pages,
+        } => print_list(output, &aha_request.list_users(&pages.paging()).await?),
+        User::Get { user_id } => print_record(output, &aha_request.get_user(user_id).await?),
+    }
+}
+
+/// Resolves an `--assignee` given as an email to the user's id.
+async fn assignee_id(
+    aha_request: &AhaRequest,
+    assignee: &Option<String>,
+) -> Result<Option<String>> {
+    match assignee {
+        Some(assignee) => Ok(aha_request
+            .resolve_user_ids(std::slice::from_ref(assignee))
+            .await?
+            .pop()),
+        None => Ok(None),
+    }
+}
+
+async fn run_todo(ctx: &Context, cmd: &Todo) -> Result<()> {
+    let (aha_request, output) = (&ctx.aha_request, &ctx.output);
+    match cmd {
+        Todo::List {
+            record_id: Some(record_id),
+            kind,
+            pages,
+        } => print_list(
+            output,
+            &aha_request
+                .list_todos(record_kind(record_id, kind)?, record_id, &pages.paging())
+                .await?,
+        ),
+        Todo::List {
+            record_id: None,
+            pages,
+            ..
+        } => print_list(output, &aha_request.list_my_todos(&pages.paging()).await?),
+        Todo::Get { todo_id } => print_record(output, &aha_request.get_todo(todo_id).await?),
+        Todo::Create {
+            record_id,
+            name,
+            body,
+            assignees,
+            due_date,
+            kind,
+        } => {
+            let todo = NewTodo {
+                name: name.clone(),
+                body: body.clone(),
+                due_date: due_date.clone(),
+                assigned_to_users: aha_request.resolve_user_ids(assignees).await?,
+            };
+            print_saved(
+                output,
+                &aha_request
+                    .create_todo(record_kind(record_id, kind)?, record_id, &todo)
+                    .await?,
+            )
+        }
+        Todo::Complete { todo_ids } => {
+            let mut todos = Vec::with_capacity(todo_ids.len());
+            for todo_id in todo_ids {
+                let todo = match aha_request.complete_todo(todo_id).await? {
+                    Some(todo) => todo,
+                    None => aha_request.get_todo(todo_id).await?,
+                };
+                todos.push(todo);
+            }
+            print!("{}", output.records(&todos)?);
+            Ok(())
+        }
+    }
+}
+
+/// The kind given with `--type`, or the one the reference number implies.
+fn record_kind(record_id: &str, kind: &Option<RecordKind>) -> Result<RecordKind> {
+    kind.or_else(|| RecordKind::from_reference(record_id))
+        .ok_or_else(|| {
+            Error::Validation(format!(
+                "can't tell what kind of record '{}' is, pass --type",
+                record_id
+            ))
+        })
+}
+
+/// Text from the argument, the file, or stdin when the argument is `-` or
+/// neither is given.
+fn read_body(body: &Option<String>, file: &Option<PathBuf>) -> Result<String> {
+    match (body.as_deref(), file) {
+        (Some(body), _) if body != "-" => Ok(body.to_string()),
+        (_, Some(file)) => std::fs::read_to_string(file).map_err(|err| {
+            Error::Validation(format!("could not read {}: {}", file.display(), err))
+        }),
+        _ => {
+            let mut body = String::new();
+            io::Read::read_to_string(&mut io::stdin(), &mut body)
+                .map_err(|err| Error::Validation(format!("could not read stdin: {}", err)))?;
+            Ok(body)
+        }
+    }
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    use pulldown_cmark::{html, Options, Parser};
+
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new_ext(markdown, options));
+    out
+}
+
+/// A row of `blimey config list`, never including the token itself.
+#[derive(Serialize)]
+struct ProfileRow {
+    name: String,
+    default: bool,
+    subdomain: String,
+    token: String,
+    base_url: Option<String>,
+    format: Option<String>,
+    product: Option<String>,
+}
+
+impl Record for ProfileRow {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "name",
+        "default",
+        "subdomain",
+        "token",
+        "base_url",
+        "format",
+        "product",
+    ];
+}
+
+fn run_config(mut config: config::Config, path: &Path, cmd: &Config) -> Result<()> {
+    match cmd {
+        Config::List => {
+            let rows: Vec<ProfileRow> = config
+                .profiles
+                .iter()
+                .map(|(name, profile)| ProfileRow {
+                    name: name.clone(),
+                    default: config.default_profile.as_deref() == Some(name),
+                    subdomain: profile.subdomain.clone(),
+                    token: match &profile.token {
+                        Some(TokenSource::Value(_)) => "stored".into(),
+                        Some(TokenSource::Env(var)) => format!("env {}", var),
+                        Some(TokenSource::Command(cmd)) => format!("command {}", cmd),
+                        None => String::new(),
+                    },
+                    base_url: profile.base_url.clone(),
+                    format: profile.format.clone(),
+                    product: profile.product.clone(),
+                })
+                .collect();
+            print!("{}", Output::new(Format::Table).records(&rows)?);
+            Ok(())
+        }
+        Config::Add {
+            name,
+            subdomain,
+            token_env,
+            token_command,
+            token,
+            base_url,
+            format,
+            product,
+            default,
+        } => {
+            if let Some(format) = format {
+                format.parse::<Format>()?;
+            }
+            let token = match (token_env, token_command, token) {
+                (Some(var), _, _) => Some(TokenSource::Env(var.clone())),
+                (_, Some(cmd), _) => Some(TokenSource::Command(cmd.clone())),
+                (_, _, Some(token)) => Some(TokenSource::Value(token.clone())),
+                _ => None,
+            };
+            let profile = Profile {
+                subdomain: subdomain.clone(),
+                token,
+                base_url: base_url.clone(),
+                format: format.clone(),
+                product: product.clone(),
+            };
+            profile.check_base_url()?;
+            config.profiles.insert(name.clone(), profile);
+            if *default || config.default_profile.is_none() {
+                config.default_profile = Some(name.clone());
+            }
+            config.save(path)?;
+            eprintln!("added profile '{}' to {}", name, path.display());
+            Ok(())
+        }
+        Config::Remove { name } => {
+            config.remove_profile(name)?;
+            config.save(path)?;
+            eprintln!("removed profile '{}' from {}", name, path.display());
+            Ok(())
         }
     }
-    surf::get("https://notfound")
 }
@@ -0,0 +1,1641 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::str::FromStr;
+
+/// The `pagination` block aha.io includes with every list response.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Pagination {
+    pub total_records: u64,
+    pub total_pages: u64,
+    pub current_page: u64,
+}
+
+/// A list response that aha.io splits across pages.
+pub trait Paginated {
+    type Record;
+
+    fn pagination(&self) -> &Pagination;
+
+    fn records(&self) -> &[Self::Record];
+
+    /// Appends the records of a later page, taking over its pagination block.
+    fn append(&mut self, next: Self);
+}
+
+macro_rules! paginated {
+    ($list:ty, $records:ident, $record:ty) => {
+        impl Paginated for $list {
+            type Record = $record;
+
+            fn pagination(&self) -> &Pagination {
+                &self.pagination
+            }
+
+            fn records(&self) -> &[$record] {
+                &self.$records
+            }
+
+            fn append(&mut self, mut next: Self) {
+                self.$records.append(&mut next.$records);
+                self.pagination = next.pagination;
+            }
+        }
+    };
+}
+
+/// A user as embedded in other records, e.g. `assigned_to_user`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct User {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A user's membership of a product, with the role they have in it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProductUser {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub role: Option<Value>,
+    #[serde(default)]
+    pub role_description: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The value of a custom field on a feature, epic, idea or release.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CustomField {
+    pub key: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub value: Value,
+    #[serde(default, rename = "type")]
+    pub field_type: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The definition of a custom field, telling what values it takes.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CustomFieldDefinition {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub field_type: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The kinds of value a custom field holds, as far as blimey parses them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CustomFieldKind {
+    Text,
+    Number,
+    Date,
+    Select,
+    MultiSelect,
+    Users,
+}
+
+impl CustomFieldDefinition {
+    /// Tells the kind from aha.io's type name, e.g.
+    /// `CustomFieldDefinitions::NumberField`. Types blimey doesn't know are
+    /// treated as text.
+    pub fn kind(&self) -> CustomFieldKind {
+        let field_type = self.field_type.as_deref().unwrap_or_default();
+        if field_type.contains("Number") {
+            CustomFieldKind::Number
+        } else if field_type.contains("Date") {
+            CustomFieldKind::Date
+        } else if field_type.contains("User") {
+            CustomFieldKind::Users
+        } else if field_type.contains("Multiple") || field_type.contains("Tags") {
+            CustomFieldKind::MultiSelect
+        } else if field_type.contains("Select") || field_type.contains("Choice") {
+            CustomFieldKind::Select
+        } else {
+            CustomFieldKind::Text
+        }
+    }
+
+    /// Parses a value given on the command line into the JSON aha.io expects
+    /// for this field. Multi-select and user fields take comma separated
+    /// values.
+    pub fn parse_value(&self, raw: &str) -> crate::Result<Value> {
+        let invalid = |expected: &str| {
+            Error::Validation(format!(
+                "invalid value '{}' for {}, expected {}",
+                raw, self.key, expected
+            ))
+        };
+        match self.kind() {
+            CustomFieldKind::Text | CustomFieldKind::Select => Ok(Value::String(raw.to_string())),
+            CustomFieldKind::Number => {
+                if let Ok(n) = raw.parse::<i64>() {
+                    return Ok(Value::from(n));
+                }
+                raw.parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| invalid("a number"))
+            }
+            CustomFieldKind::Date if is_date(raw) => Ok(Value::String(raw.to_string())),
+            CustomFieldKind::Date => Err(invalid("a date formatted YYYY-MM-DD")),
+            CustomFieldKind::MultiSelect | CustomFieldKind::Users => Ok(Value::Array(
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| Value::String(v.to_string()))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let number =
+        |part: &str, len: usize| part.len() == len && part.chars().all(|c| c.is_ascii_digit());
+    match parts.as_slice() {
+        [year, month, day] if number(year, 4) && number(month, 2) && number(day, 2) => {
+            matches!(month.parse::<u32>(), Ok(1..=12)) && matches!(day.parse::<u32>(), Ok(1..=31))
+        }
+        _ => false,
+    }
+}
+
+/// The workflow status of a release or feature.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WorkflowStatus {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub position: Option<i64>,
+    #[serde(default)]
+    pub complete: Option<bool>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A workflow and the statuses a record using it moves through.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Workflow {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub workflow_statuses: Vec<WorkflowStatus>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+impl Workflow {
+    /// Finds a status by id or by name, ignoring case.
+    pub fn find_status(&self, status: &str) -> Option<&WorkflowStatus> {
+        self.workflow_statuses
+            .iter()
+            .find(|s| s.id == status || s.name.eq_ignore_ascii_case(status.trim()))
+    }
+}
+
+/// An aha.io product (workspace).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Product {
+    pub id: String,
+    #[serde(default)]
+    pub reference_prefix: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub product_line: Option<bool>,
+    #[serde(default)]
+    pub workspace_type: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io release.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Release {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub release_date: Option<String>,
+    #[serde(default)]
+    pub parking_lot: Option<bool>,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub owner: Option<User>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub release_phases: Vec<ReleasePhase>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub record_links: Vec<RecordLink>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A phase of a release's schedule, or a milestone when it is a single day.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ReleasePhase {
+    pub id: String,
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub phase_type: Option<String>,
+    #[serde(default)]
+    pub start_on: Option<String>,
+    #[serde(default)]
+    pub end_on: Option<String>,
+    #[serde(default)]
+    pub progress: Option<f64>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io initiative, a strategic theme features and epics roll up to.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Initiative {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub progress: Option<f64>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io goal, a measurable objective of a product.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Goal {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub progress: Option<f64>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub success_metric: Option<Value>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io persona, a profile of the people a product is built for.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Persona {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io competitor, a profile of another product in the market.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Competitor {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io feature.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Feature {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub assigned_to_user: Option<User>,
+    #[serde(default)]
+    pub release: Option<Release>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub original_estimate: Option<f64>,
+    #[serde(default)]
+    pub remaining_estimate: Option<f64>,
+    #[serde(default)]
+    pub work_done: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<Vec<Requirement>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initiative: Option<Initiative>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<Goal>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub record_links: Vec<RecordLink>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub integration_fields: Vec<IntegrationField>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io epic, a group of features (formerly master feature).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Epic {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub progress: Option<f64>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub assigned_to_user: Option<User>,
+    #[serde(default)]
+    pub release: Option<Release>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initiative: Option<Initiative>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<Goal>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub record_links: Vec<RecordLink>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Progress of an epic added up from its features. Estimates are summed as
+/// aha.io reports them, in minutes or points depending on the workspace.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct FeatureRollup {
+    pub total_features: u64,
+    pub done_features: u64,
+    pub original_estimate: f64,
+    pub remaining_estimate: f64,
+    pub work_done: f64,
+}
+
+impl FeatureRollup {
+    /// A feature counts as done when its workflow status is complete.
+    pub fn from_features(features: &[Feature]) -> Self {
+        let mut rollup = Self::default();
+        for feature in features {
+            rollup.total_features += 1;
+            if feature
+                .workflow_status
+                .as_ref()
+                .and_then(|status| status.complete)
+                .unwrap_or(false)
+            {
+                rollup.done_features += 1;
+            }
+            rollup.original_estimate += feature.original_estimate.unwrap_or_default();
+            rollup.remaining_estimate += feature.remaining_estimate.unwrap_or_default();
+            rollup.work_done += feature.work_done.unwrap_or_default();
+        }
+        rollup
+    }
+}
+
+/// Work logged against a feature or requirement. `work_done` is in minutes
+/// when `work_done_units` is 10 and in points when it is 20.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TimeTrackingEvent {
+    pub id: String,
+    #[serde(default)]
+    pub work_done: Option<f64>,
+    #[serde(default)]
+    pub work_done_units: Option<u32>,
+    #[serde(default)]
+    pub work_date: Option<String>,
+    #[serde(default)]
+    pub description: Option<Value>,
+    #[serde(default)]
+    pub user: Option<User>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A value an integration keeps on a feature or requirement, e.g. the `key`
+/// and `url` of the Jira issue it is linked to.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct IntegrationField {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub value: Value,
+    #[serde(default)]
+    pub integration_id: Option<String>,
+    #[serde(default)]
+    pub service_name: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+impl IntegrationField {
+    /// The value as text, integrations store ids as numbers now and then.
+    pub fn text(&self) -> Option<String> {
+        match &self.value {
+            Value::String(value) => Some(value.clone()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// The issue an integration links a record to, gathered from the record's
+/// integration fields.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LinkedIssue {
+    pub integration_id: Option<String>,
+    pub service_name: Option<String>,
+    pub key: Option<String>,
+    pub url: Option<String>,
+}
+
+impl LinkedIssue {
+    /// One linked issue per integration that has a `key` or `url` field.
+    pub fn from_fields(fields: &[IntegrationField]) -> Vec<LinkedIssue> {
+        let mut issues: Vec<LinkedIssue> = Vec::new();
+        for field in fields {
+            if field.name != "key" && field.name != "url" {
+                continue;
+            }
+            let index = match issues
+                .iter()
+                .position(|issue| issue.integration_id == field.integration_id)
+            {
+                Some(index) => index,
+                None => {
+                    issues.push(LinkedIssue {
+                        integration_id: field.integration_id.clone(),
+                        service_name: field.service_name.clone(),
+                        ..Default::default()
+                    });
+                    issues.len() - 1
+                }
+            };
+            if field.name == "key" {
+                issues[index].key = field.text();
+            } else {
+                issues[index].url = field.text();
+            }
+        }
+        issues
+    }
+}
+
+/// An epic together with the roll-up of its features.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EpicWithRollup {
+    #[serde(flatten)]
+    pub epic: Epic,
+    pub rollup: FeatureRollup,
+}
+
+/// A requirement, one of the pieces a feature is broken down into.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Requirement {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub position: Option<i64>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub assigned_to_user: Option<User>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub record_links: Vec<RecordLink>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub integration_fields: Vec<IntegrationField>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An idea category, e.g. the portal area an idea was filed under.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct IdeaCategory {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io idea.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Idea {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub product_id: Option<String>,
+    #[serde(default)]
+    pub description: Option<Value>,
+    #[serde(default)]
+    pub workflow_status: Option<WorkflowStatus>,
+    #[serde(default)]
+    pub votes: Option<i64>,
+    #[serde(default)]
+    pub categories: Vec<IdeaCategory>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub record_links: Vec<RecordLink>,
+    #[serde(default)]
+    pub created_by_user: Option<User>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A vote on an idea, possibly cast on behalf of an organization.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Endorsement {
+    pub id: String,
+    #[serde(default)]
+    pub value: Option<i64>,
+    #[serde(default)]
+    pub idea_organization: Option<Value>,
+    #[serde(default)]
+    pub endorsed_by_portal_user: Option<Value>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A comment on an aha.io record.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Comment {
+    pub id: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub user: Option<User>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An aha.io to-do (called a task in the API), including approvals.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Todo {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub assigned_to_users: Vec<User>,
+    #[serde(default)]
+    pub created_by_user: Option<User>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A file attached to a record.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Attachment {
+    pub id: String,
+    #[serde(default)]
+    pub file_name: Option<String>,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The fields of a feature to create. Only `name` is required; workflow
+/// status and assignee accept either a name/email or an id.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewFeature {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to_user: Option<String>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "comma_separated"
+    )]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    /// An estimate as aha.io writes it, e.g. `3d`, `4h` or `5p`.
+    #[serde(
+        rename = "initial_estimate_text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub initial_estimate: Option<String>,
+}
+
+/// aha.io takes tags on write as a single comma separated string.
+fn comma_separated<S: Serializer>(values: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&values.join(","))
+}
+
+/// aha.io takes to-do assignees as a list of users, each given by email or id.
+fn assignees<S: Serializer>(values: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeSeq;
+
+    let mut seq = serializer.serialize_seq(Some(values.len()))?;
+    for value in values {
+        let key = if value.contains('@') { "email" } else { "id" };
+        let mut user = Map::new();
+        user.insert(key.to_string(), Value::String(value.clone()));
+        seq.serialize_element(&user)?;
+    }
+    seq.end()
+}
+
+/// The fields of an initiative or goal to create. Only `name` is required.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewStrategy {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+}
+
+/// The fields of an initiative or goal to change; fields left as `None` are
+/// kept. Setting `progress` only sticks when the record's progress is
+/// entered manually.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StrategyUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+}
+
+impl StrategyUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.workflow_status.is_none() && self.progress.is_none()
+    }
+}
+
+/// The fields of a persona or competitor to create. Only `name` is required;
+/// custom fields are keyed by their key.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewResearchRecord {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub custom_fields: Map<String, Value>,
+}
+
+/// The fields of a persona or competitor to change; fields left as `None`
+/// and custom fields not given are kept.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ResearchRecordUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub custom_fields: Map<String, Value>,
+}
+
+impl ResearchRecordUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none() && self.custom_fields.is_empty()
+    }
+}
+
+/// The fields of an epic to create. Only `name` is required.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewEpic {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to_user: Option<String>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "comma_separated"
+    )]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+}
+
+/// The fields of an epic to change; fields left as `None` are kept.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EpicUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to_user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+}
+
+impl EpicUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.workflow_status.is_none()
+            && self.assigned_to_user.is_none()
+            && self.start_date.is_none()
+            && self.due_date.is_none()
+    }
+}
+
+/// The fields of a feature to change; fields left as `None` are kept.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct FeatureUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    /// Estimates as aha.io writes them, e.g. `3d`, `4h` or `5p`.
+    #[serde(
+        rename = "original_estimate_text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub original_estimate: Option<String>,
+    #[serde(
+        rename = "remaining_estimate_text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub remaining_estimate: Option<String>,
+    #[serde(rename = "work_done_text", skip_serializing_if = "Option::is_none")]
+    pub work_done: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub custom_fields: Map<String, Value>,
+}
+
+impl FeatureUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.start_date.is_none()
+            && self.due_date.is_none()
+            && self.workflow_status.is_none()
+            && self.original_estimate.is_none()
+            && self.remaining_estimate.is_none()
+            && self.work_done.is_none()
+            && self.custom_fields.is_empty()
+    }
+}
+
+/// An amount of work as written on the command line, e.g. `2h`, `1h30m`,
+/// `1d` or `3p`. A day counts as 8 hours; points can't be mixed with time.
+/// Zero is a valid amount, e.g. the remaining estimate of finished work.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkAmount {
+    Minutes(f64),
+    Points(f64),
+}
+
+impl WorkAmount {
+    pub fn value(self) -> f64 {
+        match self {
+            WorkAmount::Minutes(value) | WorkAmount::Points(value) => value,
+        }
+    }
+
+    /// The unit id aha.io uses for `work_done_units`.
+    pub fn units(self) -> u32 {
+        match self {
+            WorkAmount::Minutes(_) => 10,
+            WorkAmount::Points(_) => 20,
+        }
+    }
+}
+
+impl FromStr for WorkAmount {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let invalid = || {
+            Error::Validation(format!(
+                "'{}' is not an amount of work, expected e.g. 2h, 1h30m, 1d or 3p",
+                s
+            ))
+        };
+        let text: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let (mut minutes, mut points) = (None, None);
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            let split = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .ok_or_else(invalid)?;
+            let number: f64 = rest[..split].parse().map_err(|_| invalid())?;
+            let unit = rest[split..].chars().next().ok_or_else(invalid)?;
+            let factor = match unit.to_ascii_lowercase() {
+                'm' => 1.0,
+                'h' => 60.0,
+                'd' => 8.0 * 60.0,
+                'p' => {
+                    *points.get_or_insert(0.0) += number;
+                    rest = &rest[split + 1..];
+                    continue;
+                }
+                _ => return Err(invalid()),
+            };
+            *minutes.get_or_insert(0.0) += number * factor;
+            rest = &rest[split + unit.len_utf8()..];
+        }
+        match (minutes, points) {
+            (Some(minutes), None) => Ok(WorkAmount::Minutes(minutes)),
+            (None, Some(points)) => Ok(WorkAmount::Points(points)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The fields of a requirement to create. Only `name` is required.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewRequirement {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to_user: Option<String>,
+}
+
+/// The fields of a requirement to change; fields left as `None` are kept.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RequirementUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to_user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i64>,
+}
+
+impl RequirementUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.workflow_status.is_none()
+            && self.assigned_to_user.is_none()
+            && self.position.is_none()
+    }
+}
+
+/// Time or points logged against a feature or requirement.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewTimeTrackingEvent {
+    pub work_done: f64,
+    pub work_done_units: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl NewTimeTrackingEvent {
+    pub fn new(amount: WorkAmount) -> Self {
+        NewTimeTrackingEvent {
+            work_done: amount.value(),
+            work_done_units: amount.units(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The fields of a release phase to create. `phase_type` is `phase` or
+/// `milestone`; a milestone without an `end_on` ends on its `start_on`.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewReleasePhase {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub phase_type: String,
+    pub start_on: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The fields of a release phase to change; fields left as `None` are kept.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReleasePhaseUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ReleasePhaseUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.start_on.is_none()
+            && self.end_on.is_none()
+            && self.description.is_none()
+    }
+}
+
+/// Checks a date is YYYY-MM-DD.
+pub(crate) fn check_date(date: &str) -> crate::Result<()> {
+    if is_date(date) {
+        Ok(())
+    } else {
+        Err(Error::Validation(format!(
+            "{} is not a date, expected YYYY-MM-DD",
+            date
+        )))
+    }
+}
+
+/// Checks phase dates are YYYY-MM-DD and that a phase does not end before
+/// it starts.
+pub(crate) fn check_phase_dates(start_on: Option<&str>, end_on: Option<&str>) -> crate::Result<()> {
+    for date in start_on.iter().chain(end_on.iter()) {
+        check_date(date)?;
+    }
+    match (start_on, end_on) {
+        (Some(start), Some(end)) if end < start => Err(Error::Validation(format!(
+            "a phase cannot end on {} before it starts on {}",
+            end, start
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// The fields of a to-do to create. Assignees are emails or user ids.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewTodo {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", serialize_with = "assignees")]
+    pub assigned_to_users: Vec<String>,
+}
+
+/// The fields of an idea to create. Only `name` is required.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewIdea {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "comma_separated"
+    )]
+    pub categories: Vec<String>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "comma_separated"
+    )]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_status: Option<String>,
+}
+
+/// A vote to add to an idea on behalf of a portal user and their
+/// organization.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NewEndorsement {
+    /// Email of the portal user the vote is cast for.
+    pub email: String,
+    /// Name or id of the organization the vote is cast for.
+    #[serde(
+        rename = "idea_organization_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub organization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Filters for `GET /products/:id/features`.
+#[derive(Clone, Debug, Default)]
+pub struct FeatureFilters {
+    pub tag: Option<String>,
+    pub assigned_to_user: Option<String>,
+    pub updated_since: Option<String>,
+}
+
+/// Filters for `GET /products/:id/ideas`.
+#[derive(Clone, Debug, Default)]
+pub struct IdeaFilters {
+    pub workflow_status: Option<String>,
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub updated_since: Option<String>,
+}
+
+/// The kinds of record blimey works with generically, e.g. to comment on
+/// them or list their attachments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecordKind {
+    Feature,
+    Requirement,
+    Idea,
+    Epic,
+    Release,
+    Comment,
+}
+
+impl RecordKind {
+    /// The path segment of the record's collection, e.g. `features`.
+    pub fn collection(self) -> &'static str {
+        match self {
+            RecordKind::Feature => "features",
+            RecordKind::Requirement => "requirements",
+            RecordKind::Idea => "ideas",
+            RecordKind::Epic => "epics",
+            RecordKind::Release => "releases",
+            RecordKind::Comment => "comments",
+        }
+    }
+
+    /// Guesses the kind from a reference number: `PROD-12` is a feature,
+    /// `PROD-12-1` a requirement, and `PROD-I-3`, `PROD-E-4` and `PROD-R-5`
+    /// an idea, epic and release. Plain ids can't be told apart.
+    pub fn from_reference(reference: &str) -> Option<Self> {
+        let parts: Vec<&str> = reference.split('-').collect();
+        let is_number = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        match parts.as_slice() {
+            [prefix, num] if !prefix.is_empty() && is_number(num) => Some(RecordKind::Feature),
+            [prefix, kind, num] if !prefix.is_empty() && is_number(num) => match *kind {
+                "I" => Some(RecordKind::Idea),
+                "E" => Some(RecordKind::Epic),
+                "R" => Some(RecordKind::Release),
+                kind if is_number(kind) => Some(RecordKind::Requirement),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for RecordKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "feature" => Ok(RecordKind::Feature),
+            "requirement" => Ok(RecordKind::Requirement),
+            "idea" => Ok(RecordKind::Idea),
+            "epic" => Ok(RecordKind::Epic),
+            "release" => Ok(RecordKind::Release),
+            "comment" => Ok(RecordKind::Comment),
+            _ => Err(Error::Validation(format!(
+                "unsupported record type '{}', expected one of: feature, requirement, idea, epic, release, comment",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for RecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            RecordKind::Feature => "feature",
+            RecordKind::Requirement => "requirement",
+            RecordKind::Idea => "idea",
+            RecordKind::Epic => "epic",
+            RecordKind::Release => "release",
+            RecordKind::Comment => "comment",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How one record is linked to another, named as read from the parent,
+/// e.g. PROD-12 depends on PROD-9.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkType {
+    RelatesTo,
+    DependsOn,
+    Duplicates,
+}
+
+impl LinkType {
+    /// The id aha.io uses for the link type.
+    pub fn id(self) -> u32 {
+        match self {
+            LinkType::RelatesTo => 10,
+            LinkType::DependsOn => 20,
+            LinkType::Duplicates => 30,
+        }
+    }
+}
+
+impl FromStr for LinkType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let words: Vec<&str> = s
+            .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+            .filter(|word| !word.is_empty())
+            .collect();
+        match words.join(" ").to_ascii_lowercase().as_str() {
+            "relates to" => Ok(LinkType::RelatesTo),
+            "depends on" => Ok(LinkType::DependsOn),
+            "duplicates" => Ok(LinkType::Duplicates),
+            _ => Err(Error::Validation(format!(
+                "unsupported link type '{}', expected one of: relates to, depends on, duplicates",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for LinkType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            LinkType::RelatesTo => "relates to",
+            LinkType::DependsOn => "depends on",
+            LinkType::Duplicates => "duplicates",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A record at either end of a record link.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LinkedRecord {
+    pub id: String,
+    #[serde(default)]
+    pub reference_num: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// A link between two records, read as parent, link type, child.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RecordLink {
+    pub id: String,
+    #[serde(default)]
+    pub link_type: Option<String>,
+    #[serde(default)]
+    pub link_type_id: Option<u32>,
+    #[serde(default)]
+    pub parent_record: Option<LinkedRecord>,
+    #[serde(default)]
+    pub child_record: Option<LinkedRecord>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Response envelope for `GET /features/:id/record_links`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RecordLinkList {
+    pub record_links: Vec<RecordLink>,
+}
+
+/// Response envelope for `GET /users`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct UserList {
+    pub users: Vec<User>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/workflows`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WorkflowList {
+    pub workflows: Vec<Workflow>,
+}
+
+/// Response envelope for `GET /features/:id/integration_fields`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct IntegrationFieldList {
+    pub integration_fields: Vec<IntegrationField>,
+}
+
+/// Response envelope for `GET /products/:id/personas`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PersonaList {
+    pub personas: Vec<Persona>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/competitors`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CompetitorList {
+    pub competitors: Vec<Competitor>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/users`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProductUserList {
+    pub users: Vec<ProductUser>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/custom_field_definitions`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CustomFieldDefinitionList {
+    pub custom_field_definitions: Vec<CustomFieldDefinition>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProductList {
+    pub products: Vec<Product>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/releases`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ReleaseList {
+    pub releases: Vec<Release>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/features`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FeatureList {
+    pub features: Vec<Feature>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/initiatives`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct InitiativeList {
+    pub initiatives: Vec<Initiative>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/goals`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct GoalList {
+    pub goals: Vec<Goal>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/epics`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EpicList {
+    pub epics: Vec<Epic>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /releases/:id/release_phases`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ReleasePhaseList {
+    pub release_phases: Vec<ReleasePhase>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /features/:id/time_tracking_events`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TimeTrackingEventList {
+    pub time_tracking_events: Vec<TimeTrackingEvent>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /features/:id/requirements`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RequirementList {
+    pub requirements: Vec<Requirement>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /me/tasks` and `GET /features/:id/tasks`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TodoList {
+    pub tasks: Vec<Todo>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /products/:id/ideas`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct IdeaList {
+    pub ideas: Vec<Idea>,
+    pub pagination: Pagination,
+}
+
+/// Response envelope for `GET /<record>/:id/comments`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CommentList {
+    pub comments: Vec<Comment>,
+    pub pagination: Pagination,
+}
+
+paginated!(UserList, users, User);
+paginated!(ProductUserList, users, ProductUser);
+paginated!(
+    CustomFieldDefinitionList,
+    custom_field_definitions,
+    CustomFieldDefinition
+);
+paginated!(ProductList, products, Product);
+paginated!(ReleaseList, releases, Release);
+paginated!(FeatureList, features, Feature);
+paginated!(InitiativeList, initiatives, Initiative);
+paginated!(GoalList, goals, Goal);
+paginated!(EpicList, epics, Epic);
+paginated!(RequirementList, requirements, Requirement);
+paginated!(TodoList, tasks, Todo);
+paginated!(ReleasePhaseList, release_phases, ReleasePhase);
+paginated!(
+    TimeTrackingEventList,
+    time_tracking_events,
+    TimeTrackingEvent
+);
+paginated!(PersonaList, personas, Persona);
+paginated!(CompetitorList, competitors, Competitor);
+paginated!(IdeaList, ideas, Idea);
+paginated!(CommentList, comments, Comment);
+
+#[derive(Deserialize)]
+pub(crate) struct ProductResponse {
+    pub product: Product,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ReleaseResponse {
+    pub release: Release,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct FeatureResponse {
+    pub feature: Feature,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ReleasePhaseResponse {
+    pub release_phase: ReleasePhase,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PersonaResponse {
+    pub persona: Persona,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CompetitorResponse {
+    pub competitor: Competitor,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TimeTrackingEventResponse {
+    pub time_tracking_event: TimeTrackingEvent,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RecordLinkResponse {
+    pub record_link: RecordLink,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RequirementResponse {
+    pub requirement: Requirement,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct IdeaResponse {
+    pub idea: Idea,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct EndorsementResponse {
+    #[serde(alias = "endorsement")]
+    pub idea_endorsement: Endorsement,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct EpicResponse {
+    pub epic: Epic,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct InitiativeResponse {
+    pub initiative: Initiative,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct GoalResponse {
+    pub goal: Goal,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CommentResponse {
+    pub comment: Comment,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TodoResponse {
+    pub task: Todo,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct UserResponse {
+    pub user: User,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AttachmentResponse {
+    pub attachment: Attachment,
+}
@@ -0,0 +1,506 @@
+use crate::error::{Error, Result};
+use crate::models::{
+    Attachment, Comment, Competitor, CustomField, CustomFieldDefinition, Endorsement, Epic,
+    EpicWithRollup, Feature, Goal, Idea, Initiative, IntegrationField, LinkedIssue, Paginated,
+    Persona, Product, ProductUser, RecordLink, Release, ReleasePhase, Requirement,
+    TimeTrackingEvent, Todo, User,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const COLUMN_GAP: &str = "  ";
+const MIN_COLUMN_WIDTH: usize = 6;
+
+/// A model that can be printed as a row of a table.
+///
+/// Columns are dotted paths into the record's JSON representation, e.g.
+/// `workflow_status.name` or `assigned_to_user.email`.
+pub trait Record: Serialize {
+    /// The columns shown when the user didn't pick any.
+    const DEFAULT_COLUMNS: &'static [&'static str];
+
+    /// The columns exported to CSV when the user didn't pick any.
+    const EXPORT_COLUMNS: &'static [&'static str] = Self::DEFAULT_COLUMNS;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Json,
+    Ndjson,
+    Csv,
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            "table" => Ok(Format::Table),
+            _ => Err(Error::Validation(format!(
+                "unsupported format '{}', expected one of: json, ndjson, csv, table",
+                s
+            ))),
+        }
+    }
+}
+
+/// Renders records in the format the user asked for.
+#[derive(Clone, Debug)]
+pub struct Output {
+    pub format: Format,
+    /// Columns to show instead of the record's defaults.
+    pub columns: Option<Vec<String>>,
+    /// Maximum line width for tables; `None` never truncates.
+    pub width: Option<usize>,
+}
+
+impl Output {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            columns: None,
+            width: None,
+        }
+    }
+
+    pub fn record<T: Record>(&self, record: &T) -> Result<String> {
+        match self.format {
+            Format::Json | Format::Ndjson => Ok(format!("{}\n", serde_json::to_string(record)?)),
+            Format::Csv => self.csv(std::slice::from_ref(record)),
+            Format::Table => self.table(std::slice::from_ref(record)),
+        }
+    }
+
+    pub fn list<L>(&self, list: &L) -> Result<String>
+    where
+        L: Paginated + Serialize,
+        L::Record: Record,
+    {
+        match self.format {
+            Format::Json => Ok(format!("{}\n", serde_json::to_string(list)?)),
+            Format::Ndjson => ndjson(list.records()),
+            Format::Csv => self.csv(list.records()),
+            Format::Table => self.table(list.records()),
+        }
+    }
+
+    /// Renders records that don't come from a paginated endpoint; JSON
+    /// output is a plain array.
+    pub fn records<T: Record>(&self, records: &[T]) -> Result<String> {
+        match self.format {
+            Format::Json => Ok(format!("{}\n", serde_json::to_string(records)?)),
+            Format::Ndjson => ndjson(records),
+            Format::Csv => self.csv(records),
+            Format::Table => self.table(records),
+        }
+    }
+
+    /// Renders records shown below a single record, e.g. its custom fields.
+    /// `columns` is picked for the main record, so these keep their defaults.
+    pub fn details<T: Record>(&self, records: &[T]) -> Result<String> {
+        Output {
+            columns: None,
+            ..self.clone()
+        }
+        .records(records)
+    }
+
+    fn columns(&self, defaults: &[&str]) -> Vec<String> {
+        match &self.columns {
+            Some(columns) => columns.clone(),
+            None => defaults.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn table<T: Record>(&self, records: &[T]) -> Result<String> {
+        let columns = self.columns(T::DEFAULT_COLUMNS);
+        Ok(render_table(
+            &columns,
+            &rows(records, &columns)?,
+            self.width,
+        ))
+    }
+
+    fn csv<T: Record>(&self, records: &[T]) -> Result<String> {
+        let columns = self.columns(T::EXPORT_COLUMNS);
+        Ok(render_csv(&columns, &rows(records, &columns)?))
+    }
+}
+
+fn rows<T: Record>(records: &[T], columns: &[String]) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::with_capacity(records.len());
+    for record in records {
+        let json = serde_json::to_value(record)?;
+        rows.push(columns.iter().map(|c| cell(&json, c)).collect());
+    }
+    Ok(rows)
+}
+
+fn ndjson<T: Serialize>(records: &[T]) -> Result<String> {
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&serde_json::to_string(record)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Writes a header line and one line per row, quoting fields as RFC 4180
+/// describes.
+pub fn render_csv(columns: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    for row in std::iter::once(columns).chain(rows.iter().map(|r| r.as_slice())) {
+        let fields: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Looks up a dotted path in a JSON value and renders it as a single cell.
+/// Inside a list of custom fields a path segment picks the field with that
+/// key, so `custom_fields.score` is the value of the score field.
+pub fn cell(json: &Value, path: &str) -> String {
+    let value = path
+        .split('.')
+        .try_fold(json, |value, key| match value {
+            Value::Array(fields) => fields
+                .iter()
+                .find(|field| field.get("key").and_then(Value::as_str) == Some(key))
+                .and_then(|field| field.get("value")),
+            _ => value.get(key),
+        })
+        .unwrap_or(&Value::Null);
+    cell_value(value)
+}
+
+fn cell_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(values) => values
+            .iter()
+            .map(cell_value)
+            .collect::<Vec<String>>()
+            .join(", "),
+        Value::Object(object) => match object.get("name") {
+            Some(name) => cell_value(name),
+            None => value.to_string(),
+        },
+    }
+}
+
+/// Lays out rows as aligned columns. When `width` is set the widest columns
+/// are shrunk, and their cells truncated, until every line fits.
+pub fn render_table(columns: &[String], rows: &[Vec<String>], width: Option<usize>) -> String {
+    let header: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|h| h.width()).collect();
+    for row in rows {
+        for (i, value) in row.iter().enumerate() {
+            widths[i] = widths[i].max(single_line(value).width());
+        }
+    }
+    if let Some(width) = width {
+        fit_widths(&mut widths, width);
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, &w)| pad(&truncate(&single_line(value), w), w))
+            .collect();
+        out.push_str(cells.join(COLUMN_GAP).trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn fit_widths(widths: &mut [usize], max_width: usize) {
+    let gaps = COLUMN_GAP.len() * widths.len().saturating_sub(1);
+    while widths.iter().sum::<usize>() + gaps > max_width {
+        let (widest, &w) = match widths.iter().enumerate().max_by_key(|(_, &w)| w) {
+            Some(widest) => widest,
+            None => return,
+        };
+        if w <= MIN_COLUMN_WIDTH {
+            return;
+        }
+        widths[widest] = w - 1;
+    }
+}
+
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n', '\t'], " ")
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.width() <= width {
+        return value.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in value.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+fn pad(value: &str, width: usize) -> String {
+    let padding = width.saturating_sub(value.width());
+    format!("{}{}", value, " ".repeat(padding))
+}
+
+impl Record for User {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "name", "email"];
+}
+
+impl Record for ProductUser {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "name", "email", "role_description"];
+}
+
+impl Record for Product {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["reference_prefix", "name", "workspace_type", "created_at"];
+    const EXPORT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "reference_prefix",
+        "name",
+        "workspace_type",
+        "product_line",
+        "created_at",
+        "updated_at",
+        "url",
+    ];
+}
+
+impl Record for Release {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "start_date",
+        "release_date",
+        "owner.name",
+    ];
+    const EXPORT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "start_date",
+        "release_date",
+        "parking_lot",
+        "owner.email",
+        "product_id",
+        "created_at",
+        "updated_at",
+        "url",
+    ];
+}
+
+impl Record for Persona {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "name", "updated_at"];
+}
+
+impl Record for Competitor {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "name", "updated_at"];
+}
+
+impl Record for Initiative {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "progress",
+        "start_date",
+        "end_date",
+    ];
+}
+
+impl Record for Goal {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["reference_num", "name", "workflow_status.name", "progress"];
+}
+
+impl Record for Feature {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "start_date",
+        "due_date",
+        "assigned_to_user.name",
+    ];
+    const EXPORT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "assigned_to_user.email",
+        "release.reference_num",
+        "start_date",
+        "due_date",
+        "tags",
+        "product_id",
+        "created_at",
+        "updated_at",
+        "url",
+    ];
+}
+
+impl Record for IntegrationField {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["service_name", "integration_id", "name", "value"];
+}
+
+impl Record for LinkedIssue {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["service_name", "key", "url"];
+}
+
+impl Record for TimeTrackingEvent {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "work_date",
+        "work_done",
+        "work_done_units",
+        "user.name",
+        "description",
+    ];
+}
+
+impl Record for RecordLink {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "parent_record.reference_num",
+        "link_type",
+        "child_record.reference_num",
+        "child_record.name",
+    ];
+}
+
+impl Record for ReleasePhase {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["id", "name", "type", "start_on", "end_on", "progress"];
+}
+
+impl Record for Requirement {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "position",
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "assigned_to_user.name",
+    ];
+}
+
+impl Record for Idea {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "votes",
+        "categories",
+        "created_at",
+    ];
+    const EXPORT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "votes",
+        "categories",
+        "tags",
+        "created_by_user.email",
+        "created_at",
+        "updated_at",
+        "url",
+    ];
+}
+
+impl Record for Endorsement {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["id", "value", "idea_organization.name", "created_at"];
+}
+
+impl Record for Comment {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "user.name", "created_at", "body"];
+}
+
+impl Record for Epic {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "start_date",
+        "due_date",
+        "assigned_to_user.name",
+    ];
+    const EXPORT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "assigned_to_user.email",
+        "release.reference_num",
+        "start_date",
+        "due_date",
+        "progress",
+        "tags",
+        "created_at",
+        "updated_at",
+        "url",
+    ];
+}
+
+impl Record for EpicWithRollup {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "reference_num",
+        "name",
+        "workflow_status.name",
+        "rollup.done_features",
+        "rollup.total_features",
+        "rollup.original_estimate",
+        "rollup.remaining_estimate",
+    ];
+}
+
+impl Record for Todo {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["id", "name", "status", "due_date", "assigned_to_users"];
+}
+
+impl Record for CustomField {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["key", "name", "value"];
+}
+
+impl Record for CustomFieldDefinition {
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["key", "name", "type"];
+}
+
+impl Record for Attachment {
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["id", "file_name", "file_size", "content_type", "created_at"];
+}
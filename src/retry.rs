@@ -0,0 +1,81 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use surf::http::other::RetryAfter;
+
+/// How `AhaRequest` retries requests that aha.io rejects with
+/// `429 Too Many Requests` or `503 Service Unavailable`.
+///
+/// When the response says how long to wait (`Retry-After` in seconds or as
+/// an HTTP date, or `X-Ratelimit-Reset`) that hint is used, otherwise the
+/// delay grows exponentially from `base_delay`, with jitter. Either way no
+/// delay is longer than `max_delay`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn should_retry(&self, status: surf::StatusCode, retry: u32) -> bool {
+        retry < self.max_retries
+            && (status == surf::StatusCode::TooManyRequests
+                || status == surf::StatusCode::ServiceUnavailable)
+    }
+
+    /// The delay before retry number `retry` (starting at 0), given the
+    /// response that was rate limited.
+    pub fn delay(&self, retry: u32, res: &surf::Response) -> Duration {
+        server_delay(res)
+            .map(|delay| delay.min(self.max_delay))
+            .unwrap_or_else(|| self.backoff(retry))
+    }
+
+    /// Exponential backoff with "equal jitter": half of the delay is fixed,
+    /// the other half random, so concurrent clients spread out.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .checked_mul(2u32.saturating_pow(retry))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        let half = exp / 2;
+        half + half.mul_f64(fastrand::f64())
+    }
+}
+
+fn header_value(res: &surf::Response, name: &str) -> Option<String> {
+    res.header(name)
+        .map(|values| values.last().as_str().trim().to_string())
+}
+
+fn server_delay(res: &surf::Response) -> Option<Duration> {
+    if let Ok(Some(retry_after)) = RetryAfter::from_headers(res) {
+        // a date in the past means go ahead now
+        return Some(
+            retry_after
+                .duration_since(SystemTime::now())
+                .unwrap_or_default(),
+        );
+    }
+    // X-Ratelimit-Reset is the unix time at which the current window ends
+    let reset = header_value(res, "X-Ratelimit-Reset").and_then(|v| v.parse::<u64>().ok())?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now)))
+}
@@ -0,0 +1,198 @@
+use blimey::aha::AhaRequest;
+use blimey::models::{Attachment, RecordKind};
+use blimey::Error;
+use mockito::{mock, Matcher};
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_list_attachments_of_requirement() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/requirements/PROD-12-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            r#"{"requirement":{"id":"7001","name":"First","attachments":[{"id":"501","file_name":"spec.pdf","file_size":2048,"content_type":"application/pdf","download_url":"/attachments/501/download"}]}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachments = aha
+        .list_attachments(RecordKind::Requirement, "PROD-12-1")
+        .await?;
+
+    m.assert();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].file_size, Some(2048));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_upload_attachment() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-12/attachments")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_header(
+            "Content-Type",
+            Matcher::Regex("^multipart/form-data; ?boundary=blimey-[0-9a-f]{32}$".into()),
+        )
+        .match_body(Matcher::Regex(
+            "Content-Disposition: form-data; name=\"attachment\\[data\\]\"; filename=\"mockup.png\"\r\nContent-Type: image/png\r\n\r\nnot really a png\r\n--blimey-".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"attachment":{"id":"502","file_name":"mockup.png","content_type":"image/png","file_size":16}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachment = aha
+        .upload_attachment(
+            RecordKind::Feature,
+            "PROD-12",
+            "mockup.png",
+            b"not really a png",
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(attachment.id, "502");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_download_attachment() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/attachments/503/download")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_header("Content-Type", "text/plain")
+        .with_body("hello")
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachment = Attachment {
+        id: "503".to_string(),
+        download_url: Some(format!("{}/attachments/503/download", uri)),
+        ..Attachment::default()
+    };
+    let content = aha.download_attachment(&attachment).await?;
+
+    m.assert();
+    assert_eq!(content, b"hello");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_download_attachment_follows_redirect_without_token(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let uri = &mockito::server_url();
+    let m_aha = mock("GET", "/attachments/504/download")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(302)
+        .with_header("Location", &format!("{}/storage/504", uri))
+        .create();
+    let m_storage = mock("GET", "/storage/504")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(200)
+        .with_body("stored")
+        .create();
+
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachment = Attachment {
+        id: "504".to_string(),
+        download_url: Some("/attachments/504/download".to_string()),
+        ..Attachment::default()
+    };
+    let content = aha.download_attachment(&attachment).await?;
+
+    m_aha.assert();
+    m_storage.assert();
+    assert_eq!(content, b"stored");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_download_attachment_from_foreign_host_without_token(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let uri = &mockito::server_url();
+    let m = mock("GET", "/files/505")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(200)
+        .with_body("foreign")
+        .expect(2)
+        .create();
+
+    // the same server under another host name
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachment = Attachment {
+        id: "505".to_string(),
+        download_url: Some(format!(
+            "{}/files/505",
+            uri.replace("127.0.0.1", "localhost")
+        )),
+        ..Attachment::default()
+    };
+    assert_eq!(aha.download_attachment(&attachment).await?, b"foreign");
+
+    // a base url that is only a string prefix of the download url, e.g.
+    // https://acme.aha.io against https://acme.aha.io.evil.com
+    let prefix = &uri[..uri.len() - 1];
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, prefix);
+    let attachment = Attachment {
+        id: "505".to_string(),
+        download_url: Some(format!("{}/files/505", uri)),
+        ..Attachment::default()
+    };
+    assert_eq!(aha.download_attachment(&attachment).await?, b"foreign");
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_download_attachment_follows_relative_redirect(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let m_aha = mock("GET", "/attachments/506/download")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(302)
+        .with_header("Location", "/storage/506")
+        .create();
+    let m_storage = mock("GET", "/storage/506")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(200)
+        .with_body("stored")
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachment = Attachment {
+        id: "506".to_string(),
+        download_url: Some("/attachments/506/download".to_string()),
+        ..Attachment::default()
+    };
+    let content = aha.download_attachment(&attachment).await?;
+
+    m_aha.assert();
+    m_storage.assert();
+    assert_eq!(content, b"stored");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_download_attachment_invalid_url() -> Result<(), Box<dyn std::error::Error>> {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let attachment = Attachment {
+        id: "507".to_string(),
+        download_url: Some("attachments/507/download".to_string()),
+        ..Attachment::default()
+    };
+
+    assert!(matches!(
+        aha.download_attachment(&attachment).await,
+        Err(Error::Validation(_))
+    ));
+    Ok(())
+}
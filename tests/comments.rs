@@ -0,0 +1,94 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::RecordKind;
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_list_comments_for_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/PROD-12/comments")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "comments":[{"id":"9301","body":"<p>Looks good</p>","user":{"id":"3001","name":"Jade"}}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_comments(RecordKind::Feature, "PROD-12", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.comments[0].body.as_deref(), Some("<p>Looks good</p>"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_comment_on_epic() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/epics/PROD-E-4/comments")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "comment": {"body": "<p>deployed to staging</p>"}
+        })))
+        .with_status(200)
+        .with_body(r#"{"comment":{"id":"9302","body":"<p>deployed to staging</p>"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let comment = aha
+        .create_comment(RecordKind::Epic, "PROD-E-4", "<p>deployed to staging</p>")
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(comment.id, "9302");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_comment_without_body() {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .create_comment(RecordKind::Release, "PROD-R-1", "  \n")
+        .await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+}
+
+#[test]
+fn test_record_kind_from_reference() {
+    assert_eq!(
+        RecordKind::from_reference("PROD-12"),
+        Some(RecordKind::Feature)
+    );
+    assert_eq!(
+        RecordKind::from_reference("PROD-12-1"),
+        Some(RecordKind::Requirement)
+    );
+    assert_eq!(
+        RecordKind::from_reference("PROD-I-3"),
+        Some(RecordKind::Idea)
+    );
+    assert_eq!(
+        RecordKind::from_reference("PROD-E-4"),
+        Some(RecordKind::Epic)
+    );
+    assert_eq!(
+        RecordKind::from_reference("PROD-R-5"),
+        Some(RecordKind::Release)
+    );
+    assert_eq!(RecordKind::from_reference("6776858345"), None);
+    assert_eq!(RecordKind::from_reference("PROD-S-1"), None);
+}
@@ -0,0 +1,148 @@
+use blimey::config::{Config, Profile, TokenSource};
+use blimey::Error;
+use std::path::PathBuf;
+
+const CONFIG: &str = r#"
+default_profile = "production"
+
+[profiles.production]
+subdomain = "acme"
+token = { env = "BLIMEY_TEST_PRODUCTION_TOKEN" }
+format = "table"
+product = "PROD"
+
+[profiles.sandbox]
+subdomain = "acme-sandbox"
+base_url = "https://acme-sandbox.example.com"
+token = { command = "echo sandbox-token" }
+"#;
+
+fn temp_config_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("blimey-test-{}-{}", name, std::process::id()))
+        .join("config.toml")
+}
+
+#[test]
+fn test_profile_selection() -> Result<(), Box<dyn std::error::Error>> {
+    let config: Config = toml::from_str(CONFIG)?;
+
+    let default = config.profile(None)?.unwrap();
+    assert_eq!(default.subdomain, "acme");
+    assert_eq!(default.product.as_deref(), Some("PROD"));
+
+    let sandbox = config.profile(Some("sandbox"))?.unwrap();
+    assert_eq!(
+        sandbox.base_url.as_deref(),
+        Some("https://acme-sandbox.example.com")
+    );
+
+    assert!(matches!(
+        config.profile(Some("staging")),
+        Err(Error::Config(_))
+    ));
+    assert!(Config::default().profile(None)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_token_sources() -> Result<(), Box<dyn std::error::Error>> {
+    let config: Config = toml::from_str(CONFIG)?;
+
+    std::env::set_var("BLIMEY_TEST_PRODUCTION_TOKEN", "production-token");
+    let production = config.profile(Some("production"))?.unwrap();
+    assert_eq!(
+        production.token.as_ref().unwrap().resolve()?,
+        "production-token"
+    );
+
+    let sandbox = config.profile(Some("sandbox"))?.unwrap();
+    assert_eq!(sandbox.token.as_ref().unwrap().resolve()?, "sandbox-token");
+
+    let missing = TokenSource::Env("BLIMEY_TEST_MISSING_TOKEN".into());
+    assert!(matches!(missing.resolve(), Err(Error::Config(_))));
+    Ok(())
+}
+
+#[test]
+fn test_save_load_and_remove() -> Result<(), Box<dyn std::error::Error>> {
+    let path = temp_config_path("roundtrip");
+    assert_eq!(Config::load(&path)?, Config::default());
+
+    let mut config = Config::default();
+    config.profiles.insert(
+        "work".into(),
+        Profile {
+            subdomain: "work".into(),
+            token: Some(TokenSource::Value("secret".into())),
+            ..Profile::default()
+        },
+    );
+    config.default_profile = Some("work".into());
+    config.save(&path)?;
+    assert_eq!(Config::load(&path)?, config);
+
+    config.remove_profile("work")?;
+    assert!(config.default_profile.is_none());
+    assert!(matches!(
+        config.remove_profile("work"),
+        Err(Error::Config(_))
+    ));
+
+    std::fs::remove_dir_all(path.parent().unwrap())?;
+    Ok(())
+}
+
+#[test]
+fn test_invalid_config() -> Result<(), Box<dyn std::error::Error>> {
+    let path = temp_config_path("invalid");
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, "[profiles.broken]\nformat = \"table\"\n")?;
+
+    assert!(matches!(Config::load(&path), Err(Error::Config(_))));
+
+    std::fs::remove_dir_all(path.parent().unwrap())?;
+    Ok(())
+}
+
+#[test]
+fn test_check_base_url() -> Result<(), Box<dyn std::error::Error>> {
+    let config: Config = toml::from_str(CONFIG)?;
+    config.profile(Some("sandbox"))?.unwrap().check_base_url()?;
+    config
+        .profile(Some("production"))?
+        .unwrap()
+        .check_base_url()?;
+
+    for base_url in &["not a url", "acme.aha.io", "ftp://acme.aha.io"] {
+        let profile = Profile {
+            subdomain: "acme".into(),
+            base_url: Some(base_url.to_string()),
+            ..Profile::default()
+        };
+        assert!(
+            matches!(profile.check_base_url(), Err(Error::Config(_))),
+            "{}",
+            base_url
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_keeps_config_private() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = temp_config_path("private");
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, "")?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))?;
+
+    Config::default().save(&path)?;
+    let mode = std::fs::metadata(&path)?.permissions().mode();
+
+    std::fs::remove_dir_all(path.parent().unwrap())?;
+    assert_eq!(mode & 0o777, 0o600);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{CustomFieldDefinition, CustomFieldKind, FeatureUpdate, RecordKind};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::{json, Map, Value};
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+fn definition(field_type: &str) -> CustomFieldDefinition {
+    serde_json::from_value(json!({
+        "id":"1","key":"field","name":"Field","type":field_type
+    }))
+    .unwrap()
+}
+
+#[async_std::test]
+async fn test_list_custom_field_definitions() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-1/custom_field_definitions")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "custom_field_definitions":[
+                    {"id":"1","key":"score","name":"Score","type":"CustomFieldDefinitions::NumberField"},
+                    {"id":"2","key":"segment","name":"Customer segment","type":"CustomFieldDefinitions::SelectMultipleConstant"}
+                ],
+                "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_custom_field_definitions("PROD-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(
+        list.custom_field_definitions[0].kind(),
+        CustomFieldKind::Number
+    );
+    assert_eq!(
+        list.custom_field_definitions[1].kind(),
+        CustomFieldKind::MultiSelect
+    );
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_set_custom_fields_on_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/PROD-12")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "feature": {"custom_fields": {"score": 8, "segment": ["Enterprise"]}}
+        })))
+        .with_status(200)
+        .with_body(
+            r#"{"feature":{"id":"1001","name":"Sign up","custom_fields":[{"key":"score","name":"Score","value":8,"type":"number"},{"key":"segment","name":"Customer segment","value":["Enterprise"],"type":"array"}]}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let mut values = Map::new();
+    values.insert("score".to_string(), json!(8));
+    values.insert("segment".to_string(), json!(["Enterprise"]));
+    let fields = aha
+        .set_custom_fields(RecordKind::Feature, "PROD-12", &values)
+        .await?;
+
+    m.assert();
+    assert_eq!(fields[0].value, json!(8));
+    assert_eq!(fields[1].field_type.as_deref(), Some("array"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_feature_with_custom_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/PROD-13")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "feature": {"name": "Sign in", "custom_fields": {"score": 5}}
+        })))
+        .with_status(200)
+        .with_body(
+            r#"{"feature":{"id":"1002","name":"Sign in","custom_fields":[{"key":"score","name":"Score","value":5,"type":"number"}]}}"#,
+        )
+        .expect(1)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let mut update = FeatureUpdate {
+        name: Some("Sign in".to_string()),
+        ..Default::default()
+    };
+    update.custom_fields.insert("score".to_string(), json!(5));
+    let feature = aha.update_feature("PROD-13", &update).await?.unwrap();
+
+    m.assert();
+    assert_eq!(feature.custom_fields[0].value, json!(5));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_set_custom_fields_on_requirement() {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let mut values = Map::new();
+    values.insert("score".to_string(), json!(8));
+    let res = aha
+        .set_custom_fields(RecordKind::Requirement, "PROD-12-1", &values)
+        .await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+}
+
+#[test]
+fn test_parse_custom_field_values() -> Result<(), Box<dyn std::error::Error>> {
+    let text = definition("CustomFieldDefinitions::LineField");
+    assert_eq!(text.parse_value("high")?, json!("high"));
+
+    let number = definition("CustomFieldDefinitions::NumberField");
+    assert_eq!(number.parse_value("8")?, json!(8));
+    assert_eq!(number.parse_value("2.5")?, json!(2.5));
+    assert!(matches!(
+        number.parse_value("eight"),
+        Err(Error::Validation(_))
+    ));
+
+    let date = definition("CustomFieldDefinitions::DateField");
+    assert_eq!(date.parse_value("2022-09-30")?, json!("2022-09-30"));
+    assert!(matches!(
+        date.parse_value("30/09/2022"),
+        Err(Error::Validation(_))
+    ));
+
+    let select = definition("CustomFieldDefinitions::SelectConstant");
+    assert_eq!(select.kind(), CustomFieldKind::Select);
+    assert_eq!(select.parse_value("Enterprise")?, json!("Enterprise"));
+
+    let multi = definition("CustomFieldDefinitions::SelectMultipleConstant");
+    assert_eq!(
+        multi.parse_value("Enterprise, SMB")?,
+        Value::from(vec!["Enterprise", "SMB"])
+    );
+
+    let users = definition("CustomFieldDefinitions::UsersField");
+    assert_eq!(users.kind(), CustomFieldKind::Users);
+    assert_eq!(
+        users.parse_value("jade@example.com")?,
+        json!(["jade@example.com"])
+    );
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{EpicUpdate, NewEpic};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+const EPIC_BODY: &str = r#"{"epic":{"id":"7001","reference_num":"PROD-E-1","name":"Onboarding","progress":40.0,"workflow_status":{"id":"1","name":"In development","complete":false}}}"#;
+
+#[async_std::test]
+async fn test_list_epics_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-1/epics")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "epics":[{"id":"7001","reference_num":"PROD-E-1","name":"Onboarding"}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_epics_for_product("PROD-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.epics[0].name, "Onboarding");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_epic_with_rollup() -> Result<(), Box<dyn std::error::Error>> {
+    let m_epic = mock("GET", "/api/v1/epics/PROD-E-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(EPIC_BODY)
+        .create();
+    let m_features = mock(
+        "GET",
+        Matcher::Regex(r"^/api/v1/epics/7001/features\?fields=[^&]*workflow_status[^&]*&page=1$".into()),
+    )
+    .match_header("Authorization", BEARER_TOKEN)
+    .with_status(200)
+    .with_body(
+        json!({
+            "features":[
+                {"id":"1","name":"Sign up","workflow_status":{"id":"1","name":"Shipped","complete":true},
+                 "original_estimate":480.0,"remaining_estimate":0.0,"work_done":540.0},
+                {"id":"2","name":"Welcome tour","workflow_status":{"id":"2","name":"In development","complete":false},
+                 "original_estimate":960.0,"remaining_estimate":600.0,"work_done":360.0},
+                {"id":"3","name":"Invite team"}
+            ],
+            "pagination":{"total_records":3,"total_pages":1,"current_page":1}
+        })
+        .to_string(),
+    )
+    .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let epic = aha.get_epic_with_rollup("PROD-E-1").await?;
+
+    m_epic.assert();
+    m_features.assert();
+    assert_eq!(epic.epic.reference_num.as_deref(), Some("PROD-E-1"));
+    assert_eq!(epic.rollup.total_features, 3);
+    assert_eq!(epic.rollup.done_features, 1);
+    assert_eq!(epic.rollup.original_estimate, 1440.0);
+    assert_eq!(epic.rollup.remaining_estimate, 600.0);
+    assert_eq!(epic.rollup.work_done, 900.0);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_epic() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/releases/PROD-R-1/epics")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "epic": {"name": "Onboarding", "tags": "growth,q3", "due_date": "2022-09-30"}
+        })))
+        .with_status(200)
+        .with_body(EPIC_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let epic = NewEpic {
+        name: "Onboarding".to_string(),
+        tags: vec!["growth".to_string(), "q3".to_string()],
+        due_date: Some("2022-09-30".to_string()),
+        ..NewEpic::default()
+    };
+    let epic = aha.create_epic("PROD-R-1", &epic).await?.unwrap();
+
+    m.assert();
+    assert_eq!(epic.id, "7001");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_epic() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/epics/PROD-E-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "epic": {"workflow_status": "In development"}
+        })))
+        .with_status(200)
+        .with_body(EPIC_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let update = EpicUpdate {
+        workflow_status: Some("In development".to_string()),
+        ..EpicUpdate::default()
+    };
+    let epic = aha.update_epic("PROD-E-1", &update).await?.unwrap();
+
+    m.assert();
+    assert_eq!(epic.progress, Some(40.0));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_epic_with_nothing_to_update() {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha.update_epic("PROD-E-1", &EpicUpdate::default()).await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+}
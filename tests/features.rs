@@ -1,4 +1,7 @@
-use blimey::aha::AhaRequest;
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{FeatureFilters, FeatureUpdate, NewFeature};
+use blimey::Error;
+use http_types::StatusCode;
 use mockito::{mock, Matcher};
 use serde_json::json;
 
@@ -11,14 +14,90 @@ async fn test_get_feature() -> Result<(), Box<dyn std::error::Error>> {
     let m = mock("GET", "/api/v1/features/FEAT-1")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(
+            json!({"feature":{
+                "id":"1001",
+                "reference_num":"FEAT-1",
+                "name":"A feature",
+                "workflow_status":{"id":"2001","name":"In development","complete":false},
+                "assigned_to_user":{"id":"3001","name":"Jade","email":"jade@example.com"},
+                "tags":["api"],
+                "custom":"kept"
+            }})
+            .to_string(),
+        )
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.get_feature("FEAT-1").await?;
+    let feature = aha.get_feature("FEAT-1").await?;
 
     m.assert();
+    assert_eq!(feature.reference_num.as_deref(), Some("FEAT-1"));
+    assert_eq!(feature.workflow_status.unwrap().name, "In development");
+    assert_eq!(
+        feature.assigned_to_user.unwrap().email.as_deref(),
+        Some("jade@example.com")
+    );
+    assert_eq!(feature.tags, vec!["api"]);
+    assert_eq!(feature.other["custom"], "kept");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_feature_error_status() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/FEAT-404")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(404)
+        .with_body(r#"{"error": "Record not found"}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    match aha.get_feature("FEAT-404").await {
+        Err(Error::Http { status, message }) => {
+            assert_eq!(status, StatusCode::NotFound);
+            assert_eq!(message, "Record not found");
+        }
+        other => panic!("expected an http error, got {:?}", other),
+    }
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_feature_invalid_body() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/FEAT-500")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body("<html>maintenance</html>")
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha.get_feature("FEAT-500").await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Serialization(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_feature_validation_error() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/FEAT-9")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .update_feature("FEAT-9", &FeatureUpdate::default())
+        .await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
     Ok(())
 }
 
@@ -27,14 +106,28 @@ async fn test_list_features_for_product() -> Result<(), Box<dyn std::error::Erro
     let m = mock("GET", "/api/v1/products/PROD-1/features")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(
+            json!({
+                "features":[
+                    {"id":"1001","reference_num":"FEAT-1","name":"One"},
+                    {"id":"1002","reference_num":"FEAT-2","name":"Two"}
+                ],
+                "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.list_features_for_product("PROD-1").await?;
+    let list = aha
+        .list_features_for_product("PROD-1", &FeatureFilters::default(), &Paging::default())
+        .await?;
 
     m.assert();
+    assert_eq!(list.features.len(), 2);
+    assert_eq!(list.features[1].name, "Two");
+    assert_eq!(list.pagination.total_records, 2);
     Ok(())
 }
 
@@ -52,12 +145,294 @@ async fn test_update_feature() -> Result<(), Box<dyn std::error::Error>> {
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
     aha.update_feature(
         "FEAT-1",
-        &Some("newname".to_string()),
-        &Some("2022-08-13".to_string()),
-        &Some("2022-08-13".to_string()),
+        &FeatureUpdate {
+            name: Some("newname".to_string()),
+            start_date: Some("2022-08-13".to_string()),
+            due_date: Some("2022-08-13".to_string()),
+            ..Default::default()
+        },
     )
     .await?;
 
     m.assert();
     Ok(())
 }
+
+#[async_std::test]
+async fn test_update_feature_no_content() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/FEAT-2")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature":{"name":"newname"}})))
+        .with_status(204)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .update_feature(
+            "FEAT-2",
+            &FeatureUpdate {
+                name: Some("newname".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    m.assert();
+    assert!(feature.is_none());
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_feature_without_body() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/releases/REL-3/features")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(201)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .create_feature(
+            "REL-3",
+            &NewFeature {
+                name: "newname".to_string(),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    m.assert();
+    assert!(feature.is_none());
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_features_for_product_page() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-2/features?page=3&per_page=10")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "features":[{"id":"1021","reference_num":"FEAT-21","name":"Twenty one"}],
+                "pagination":{"total_records":21,"total_pages":3,"current_page":3}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let paging = Paging {
+        page: Some(3),
+        per_page: Some(10),
+        all: false,
+    };
+    let list = aha
+        .list_features_for_product("PROD-2", &FeatureFilters::default(), &paging)
+        .await?;
+
+    m.assert();
+    assert_eq!(list.features.len(), 1);
+    assert_eq!(list.pagination.current_page, 3);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_all_features_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let page_1 = mock("GET", "/api/v1/products/PROD-3/features?page=1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "features":[{"id":"1001","reference_num":"FEAT-1","name":"One"}],
+                "pagination":{"total_records":2,"total_pages":2,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+    let page_2 = mock("GET", "/api/v1/products/PROD-3/features?page=2")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "features":[{"id":"1002","reference_num":"FEAT-2","name":"Two"}],
+                "pagination":{"total_records":2,"total_pages":2,"current_page":2}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_features_for_product("PROD-3", &FeatureFilters::default(), &Paging::all())
+        .await?;
+
+    page_1.assert();
+    page_2.assert();
+    let names: Vec<&str> = list.features.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["One", "Two"]);
+    assert_eq!(list.pagination.current_page, 2);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/releases/REL-1/features")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature":{
+            "name":"newname",
+            "description":"<p>Details</p>",
+            "workflow_status":"In design",
+            "assigned_to_user":"jade@example.com",
+            "tags":"api,cli",
+            "start_date":"2022-08-13",
+            "due_date":"2022-09-13",
+            "initial_estimate_text":"3d"
+        }})))
+        .with_status(201)
+        .with_body(r#"{"feature":{"id":"1003","reference_num":"FEAT-3","name":"newname"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .create_feature(
+            "REL-1",
+            &NewFeature {
+                name: "newname".to_string(),
+                description: Some("<p>Details</p>".to_string()),
+                workflow_status: Some("In design".to_string()),
+                assigned_to_user: Some("jade@example.com".to_string()),
+                tags: vec!["api".to_string(), "cli".to_string()],
+                start_date: Some("2022-08-13".to_string()),
+                due_date: Some("2022-09-13".to_string()),
+                initial_estimate: Some("3d".to_string()),
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(feature.reference_num.as_deref(), Some("FEAT-3"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_feature_with_name_only() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/releases/REL-2/features")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature":{"name":"newname"}})))
+        .with_status(201)
+        .with_body(r#"{"feature":{"id":"1004","reference_num":"FEAT-4","name":"newname"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = NewFeature {
+        name: "newname".to_string(),
+        ..NewFeature::default()
+    };
+    aha.create_feature("REL-2", &feature).await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_feature_without_name() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/releases/REL-3/features")
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha.create_feature("REL-3", &NewFeature::default()).await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_delete_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("DELETE", "/api/v1/features/FEAT-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(204)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    aha.delete_feature("FEAT-1").await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_features_for_product_by_tag() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-4/features?tag=q3")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            r#"{"features":[{"id":"1001","name":"Sign up","tags":["q3"]}],"pagination":{"total_records":1,"total_pages":1,"current_page":1}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let filters = FeatureFilters {
+        tag: Some("q3".to_string()),
+        ..FeatureFilters::default()
+    };
+    let list = aha
+        .list_features_for_product("PROD-4", &filters, &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.features[0].tags, vec!["q3"]);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_features_for_product_by_assignee_email() -> Result<(), Box<dyn std::error::Error>>
+{
+    let users = mock("GET", "/api/v1/users?page=1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "users":[{"id":"3001","name":"Jade","email":"jade@example.com"}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+    let features = mock("GET", "/api/v1/products/PROD-4/features?assigned_to_user=3001")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            r#"{"features":[{"id":"1001","name":"Sign up"}],"pagination":{"total_records":1,"total_pages":1,"current_page":1}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    // what `feature list --assignee` does with an email
+    let filters = FeatureFilters {
+        assigned_to_user: aha
+            .resolve_user_ids(&["Jade@Example.com".to_string()])
+            .await?
+            .pop(),
+        ..FeatureFilters::default()
+    };
+    let list = aha
+        .list_features_for_product("PROD-4", &filters, &Paging::default())
+        .await?;
+
+    users.assert();
+    features.assert();
+    assert_eq!(list.features.len(), 1);
+    Ok(())
+}
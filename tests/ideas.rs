@@ -0,0 +1,167 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{IdeaFilters, NewEndorsement, NewIdea};
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+const IDEA_BODY: &str = r#"{"idea":{"id":"8001","reference_num":"PROD-I-1","name":"Dark mode","votes":12,"categories":[{"id":"9001","name":"UI"}]}}"#;
+
+#[async_std::test]
+async fn test_list_ideas_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-1/ideas")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "ideas":[{"id":"8001","reference_num":"PROD-I-1","name":"Dark mode","votes":12}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_ideas_for_product("PROD-1", &IdeaFilters::default(), &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.ideas[0].votes, Some(12));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_ideas_for_product_with_filters() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock(
+        "GET",
+        "/api/v1/products/PROD-2/ideas?workflow_status=Needs%20review&category=UI&tag=mobile&updated_since=2022-08-13T15:15:15Z",
+    )
+    .match_header("Authorization", BEARER_TOKEN)
+    .with_status(200)
+    .with_body(r#"{"ideas":[],"pagination":{"total_records":0,"total_pages":0,"current_page":1}}"#)
+    .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let filters = IdeaFilters {
+        workflow_status: Some("Needs review".to_string()),
+        category: Some("UI".to_string()),
+        tag: Some("mobile".to_string()),
+        updated_since: Some("2022-08-13T15:15:15Z".to_string()),
+    };
+    let list = aha
+        .list_ideas_for_product("PROD-2", &filters, &Paging::default())
+        .await?;
+
+    m.assert();
+    assert!(list.ideas.is_empty());
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_idea() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/ideas/PROD-I-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(IDEA_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let idea = aha.get_idea("PROD-I-1").await?;
+
+    m.assert();
+    assert_eq!(idea.name, "Dark mode");
+    assert_eq!(idea.categories[0].name, "UI");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_idea() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/products/PROD-1/ideas")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"idea":{
+            "name":"Dark mode",
+            "description":"Please",
+            "categories":"UI,Themes",
+            "tags":"mobile"
+        }})))
+        .with_status(201)
+        .with_body(IDEA_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let idea = NewIdea {
+        name: "Dark mode".to_string(),
+        description: Some("Please".to_string()),
+        categories: vec!["UI".to_string(), "Themes".to_string()],
+        tags: vec!["mobile".to_string()],
+        workflow_status: None,
+    };
+    aha.create_idea("PROD-1", &idea).await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_idea_endorsement() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/ideas/PROD-I-1/endorsements")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"idea_endorsement":{
+            "email":"buyer@customer.com",
+            "idea_organization_id":"Customer Inc",
+            "value":3
+        }})))
+        .with_status(201)
+        .with_body(
+            r#"{"idea_endorsement":{"id":"9101","value":3,"idea_organization":{"id":"9201","name":"Customer Inc"}}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let endorsement = NewEndorsement {
+        email: "buyer@customer.com".to_string(),
+        organization: Some("Customer Inc".to_string()),
+        value: Some(3),
+        description: None,
+    };
+    let vote = aha
+        .create_idea_endorsement("PROD-I-1", &endorsement)
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(vote.value, Some(3));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_comments_for_idea() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/ideas/PROD-I-1/comments")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "comments":[{"id":"9301","body":"<p>+1</p>","user":{"id":"3001","name":"Jade"}}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_comments_for_idea("PROD-I-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.comments[0].body.as_deref(), Some("<p>+1</p>"));
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use blimey::aha::AhaRequest;
+use blimey::models::{IntegrationField, LinkedIssue, RecordKind};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_list_integration_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/requirements/PROD-1-1/integration_fields")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({"integration_fields":[
+                {"id":"1","name":"id","value":10042,"integration_id":"900","service_name":"jira"},
+                {"id":"2","name":"key","value":"JIRA-7","integration_id":"900","service_name":"jira"}
+            ]})
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let fields = aha
+        .list_integration_fields(RecordKind::Requirement, "PROD-1-1")
+        .await?;
+
+    m.assert();
+    assert_eq!(fields[0].text().as_deref(), Some("10042"));
+    assert_eq!(fields[1].text().as_deref(), Some("JIRA-7"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_set_integration_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-1/integrations/900/fields")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"integration_fields":[
+            {"name":"key","value":"JIRA-7"},
+            {"name":"url","value":"https://example.atlassian.net/browse/JIRA-7"}
+        ]})))
+        .with_status(200)
+        .with_body(r#"{"integration_fields":[{"name":"key","value":"JIRA-7"}]}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let fields = aha
+        .set_integration_fields(
+            RecordKind::Feature,
+            "PROD-1",
+            "900",
+            &[
+                ("key".into(), "JIRA-7".into()),
+                (
+                    "url".into(),
+                    "https://example.atlassian.net/browse/JIRA-7".into(),
+                ),
+            ],
+        )
+        .await?;
+
+    m.assert();
+    assert_eq!(fields.len(), 1);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_integration_fields_of_an_epic() -> Result<(), Box<dyn std::error::Error>> {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .list_integration_fields(RecordKind::Epic, "PROD-E-1")
+        .await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_find_feature_by_integration_key() -> Result<(), Box<dyn std::error::Error>> {
+    let list = mock(
+        "GET",
+        "/api/v1/products/PROD/features?fields=id%2Creference_num%2Cname%2Cintegration_fields&page=1",
+    )
+    .match_header("Authorization", BEARER_TOKEN)
+    .with_status(200)
+    .with_body(
+        json!({
+            "features":[
+                {"id":"1001","reference_num":"PROD-1","name":"One","integration_fields":[{"name":"key","value":"JIRA-6"}]},
+                {"id":"1002","reference_num":"PROD-2","name":"Two","integration_fields":[{"name":"key","value":"JIRA-7"}]},
+                {"id":"1003","reference_num":"PROD-3","name":"Three"}
+            ],
+            "pagination":{"total_records":3,"total_pages":1,"current_page":1}
+        })
+        .to_string(),
+    )
+    .create();
+    let get = mock("GET", "/api/v1/features/1002")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({"feature":{"id":"1002","reference_num":"PROD-2","name":"Two","integration_fields":[
+                {"name":"key","value":"JIRA-7","integration_id":"900","service_name":"jira"},
+                {"name":"url","value":"https://example.atlassian.net/browse/JIRA-7","integration_id":"900","service_name":"jira"}
+            ]}})
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .find_feature_by_integration_key("PROD", "jira-7")
+        .await?;
+
+    list.assert();
+    get.assert();
+    let issues = LinkedIssue::from_fields(&feature.integration_fields);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].key.as_deref(), Some("JIRA-7"));
+    assert_eq!(
+        issues[0].url.as_deref(),
+        Some("https://example.atlassian.net/browse/JIRA-7")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_linked_issues_per_integration() {
+    let field = |integration: &str, name: &str, value: &str| IntegrationField {
+        name: name.into(),
+        value: json!(value),
+        integration_id: Some(integration.into()),
+        ..Default::default()
+    };
+    let issues = LinkedIssue::from_fields(&[
+        field("900", "key", "JIRA-7"),
+        field("901", "url", "https://github.com/acme/app/issues/3"),
+        field("900", "id", "10042"),
+        field("900", "url", "https://example.atlassian.net/browse/JIRA-7"),
+    ]);
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].key.as_deref(), Some("JIRA-7"));
+    assert!(issues[0].url.as_deref().unwrap().ends_with("JIRA-7"));
+    assert_eq!(issues[1].key, None);
+}
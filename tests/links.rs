@@ -0,0 +1,135 @@
+use blimey::aha::AhaRequest;
+use blimey::models::{LinkType, RecordKind};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_list_record_links() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/epics/PROD-E-1/record_links")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({"record_links":[{
+                "id":"81",
+                "link_type":"Depends on",
+                "link_type_id":20,
+                "parent_record":{"id":"3001","reference_num":"PROD-E-1","name":"Epic"},
+                "child_record":{"id":"1009","reference_num":"PROD-9","name":"Nine"}
+            }]})
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let links = aha.list_record_links(RecordKind::Epic, "PROD-E-1").await?;
+
+    m.assert();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].link_type_id, Some(20));
+    assert_eq!(
+        links[0]
+            .child_record
+            .as_ref()
+            .unwrap()
+            .reference_num
+            .as_deref(),
+        Some("PROD-9")
+    );
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_record_link() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-12/record_links")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"record_link":{
+            "record_type":"Ideas::Idea",
+            "record_id":"PROD-I-3",
+            "link_type":30
+        }})))
+        .with_status(200)
+        .with_body(r#"{"record_link":{"id":"82","link_type":"Duplicates"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let link = aha
+        .create_record_link(
+            RecordKind::Feature,
+            "PROD-12",
+            LinkType::Duplicates,
+            RecordKind::Idea,
+            "PROD-I-3",
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(link.id, "82");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_record_link_to_comment() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-12/record_links")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .create_record_link(
+            RecordKind::Feature,
+            "PROD-12",
+            LinkType::RelatesTo,
+            RecordKind::Comment,
+            "44",
+        )
+        .await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_delete_record_link() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("DELETE", "/api/v1/record_links/81")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(204)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    aha.delete_record_link("81").await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[test]
+fn test_link_type_from_str() {
+    assert_eq!(
+        "depends on".parse::<LinkType>().unwrap(),
+        LinkType::DependsOn
+    );
+    assert_eq!(
+        "Relates-To".parse::<LinkType>().unwrap(),
+        LinkType::RelatesTo
+    );
+    assert_eq!(
+        "duplicates".parse::<LinkType>().unwrap(),
+        LinkType::Duplicates
+    );
+    assert!(matches!(
+        "blocks".parse::<LinkType>(),
+        Err(Error::Validation(_))
+    ));
+}
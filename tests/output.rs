@@ -0,0 +1,158 @@
+use blimey::models::{CustomField, FeatureList};
+use blimey::output::{cell, render_table, Format, Output};
+use serde_json::json;
+
+fn feature_list() -> FeatureList {
+    serde_json::from_value(json!({
+        "features":[
+            {
+                "id":"1001",
+                "reference_num":"FEAT-1",
+                "name":"Export roadmap",
+                "start_date":"2021-06-01",
+                "due_date":"2021-06-30",
+                "workflow_status":{"id":"2001","name":"In development"},
+                "assigned_to_user":{"id":"3001","name":"Jade","email":"jade@example.com"}
+            },
+            {"id":"1002","reference_num":"FEAT-2","name":"Import ideas"}
+        ],
+        "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_table_default_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::new(Format::Table);
+    let table = output.list(&feature_list())?;
+
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "REFERENCE_NUM  NAME            WORKFLOW_STATUS.NAME  START_DATE  DUE_DATE    ASSIGNED_TO_USER.NAME",
+            "FEAT-1         Export roadmap  In development        2021-06-01  2021-06-30  Jade",
+            "FEAT-2         Import ideas",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_table_selected_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output {
+        columns: Some(vec![
+            "reference_num".to_string(),
+            "assigned_to_user.email".to_string(),
+        ]),
+        ..Output::new(Format::Table)
+    };
+    let table = output.list(&feature_list())?;
+
+    assert_eq!(
+        table,
+        "REFERENCE_NUM  ASSIGNED_TO_USER.EMAIL\nFEAT-1         jade@example.com\nFEAT-2\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_details_ignore_selected_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output {
+        columns: Some(vec!["reference_num".to_string()]),
+        ..Output::new(Format::Table)
+    };
+    let fields: Vec<CustomField> =
+        serde_json::from_value(json!([{"key":"score","name":"Score","value":8}]))?;
+
+    assert_eq!(
+        output.details(&fields)?,
+        "KEY    NAME   VALUE\nscore  Score  8\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_table_truncates_to_width() {
+    let columns = vec!["ref".to_string(), "name".to_string()];
+    let rows = vec![vec![
+        "FEAT-1".to_string(),
+        "A feature with a very long name".to_string(),
+    ]];
+    let table = render_table(&columns, &rows, Some(20));
+
+    for line in table.lines() {
+        assert!(line.chars().count() <= 20, "{:?} is too wide", line);
+    }
+    assert!(table.contains("FEAT-1  A feature w…"));
+}
+
+#[test]
+fn test_unsupported_format() {
+    assert!("yaml".parse::<Format>().is_err());
+    assert_eq!("TABLE".parse::<Format>().unwrap(), Format::Table);
+}
+
+#[test]
+fn test_csv_flattens_nested_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output {
+        columns: Some(vec![
+            "reference_num".to_string(),
+            "name".to_string(),
+            "workflow_status.name".to_string(),
+            "assigned_to_user.email".to_string(),
+        ]),
+        ..Output::new(Format::Csv)
+    };
+    let mut list = feature_list();
+    list.features[1].name = "Import \"ideas\", fast".to_string();
+    let csv = output.list(&list)?;
+
+    assert_eq!(
+        csv,
+        "reference_num,name,workflow_status.name,assigned_to_user.email\r\n\
+         FEAT-1,Export roadmap,In development,jade@example.com\r\n\
+         FEAT-2,\"Import \"\"ideas\"\", fast\",,\r\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_csv_default_export_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let csv = Output::new(Format::Csv).list(&feature_list())?;
+
+    let header = csv.lines().next().unwrap();
+    assert!(header.starts_with(
+        "id,reference_num,name,workflow_status.name,assigned_to_user.email,release.reference_num,"
+    ));
+    assert_eq!(csv.lines().count(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_ndjson_one_record_per_line() -> Result<(), Box<dyn std::error::Error>> {
+    let ndjson = Output::new(Format::Ndjson).list(&feature_list())?;
+
+    let lines: Vec<serde_json::Value> = ndjson
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["reference_num"], "FEAT-1");
+    assert_eq!(lines[1]["name"], "Import ideas");
+    Ok(())
+}
+
+#[test]
+fn test_cell_picks_custom_field_by_key() {
+    let json = json!({
+        "custom_fields":[
+            {"key":"score","name":"Score","value":8},
+            {"key":"segment","name":"Customer segment","value":["Enterprise","SMB"]}
+        ]
+    });
+
+    assert_eq!(cell(&json, "custom_fields.score"), "8");
+    assert_eq!(cell(&json, "custom_fields.segment"), "Enterprise, SMB");
+    assert_eq!(cell(&json, "custom_fields.risk"), "");
+}
@@ -0,0 +1,136 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{NewResearchRecord, ResearchRecordUpdate};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_list_personas_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD/personas?page=1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "personas":[{"id":"41","name":"Ops lead"},{"id":"42","name":"Analyst"}],
+                "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let personas = aha
+        .list_personas_for_product("PROD", &Paging::all())
+        .await?;
+
+    m.assert();
+    assert_eq!(personas.personas.len(), 2);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_competitor() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/competitors/51")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({"competitor":{
+                "id":"51",
+                "name":"Acme",
+                "product_id":"PROD",
+                "custom_fields":[{"key":"pricing","name":"Pricing","value":"freemium","type":"string"}]
+            }})
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let competitor = aha.get_competitor("51").await?;
+
+    m.assert();
+    assert_eq!(competitor.custom_fields[0].key, "pricing");
+    assert_eq!(competitor.custom_fields[0].value, json!("freemium"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_persona_with_custom_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/products/PROD/personas")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"persona":{
+            "name":"Ops lead",
+            "custom_fields":{"age_range":"25-34"}
+        }})))
+        .with_status(200)
+        .with_body(r#"{"persona":{"id":"41","name":"Ops lead"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let mut persona = NewResearchRecord {
+        name: "Ops lead".into(),
+        ..Default::default()
+    };
+    persona
+        .custom_fields
+        .insert("age_range".into(), json!("25-34"));
+    let created = aha
+        .create_persona_for_product("PROD", &persona)
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(created.id, "41");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_competitor() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/competitors/51")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"competitor":{"name":"Acme Inc"}})))
+        .with_status(200)
+        .with_body(r#"{"competitor":{"id":"51","name":"Acme Inc"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let competitor = aha
+        .update_competitor(
+            "51",
+            &ResearchRecordUpdate {
+                name: Some("Acme Inc".into()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(competitor.name, "Acme Inc");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_persona_validation_error() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/personas/41")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .update_persona("41", &ResearchRecordUpdate::default())
+        .await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
@@ -1,24 +1,31 @@
-use blimey::aha::AhaRequest;
+use blimey::aha::{AhaRequest, Paging};
+use blimey::Error;
+use http_types::StatusCode;
 use mockito::{mock, Matcher};
 use serde_json::json;
 
 const TEST_TOKEN: &str = "test_token";
 const TEST_SUBDOMAIN: &str = "test_sub";
 const BEARER_TOKEN: &str = "Bearer test_token";
+const LIST_BODY: &str = r#"{"products":[{"id":"4001","reference_prefix":"PROD","name":"Product"}],"pagination":{"total_records":1,"total_pages":1,"current_page":1}}"#;
 
 #[async_std::test]
 async fn test_get_product() -> Result<(), Box<dyn std::error::Error>> {
     let m = mock("GET", "/api/v1/products/PROD-1")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(
+            r#"{"product":{"id":"4001","reference_prefix":"PROD","name":"Product","workspace_type":"product_workspace"}}"#,
+        )
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.get_product("PROD-1").await?;
+    let product = aha.get_product("PROD-1").await?;
 
     m.assert();
+    assert_eq!(product.id, "4001");
+    assert_eq!(product.reference_prefix.as_deref(), Some("PROD"));
     Ok(())
 }
 
@@ -27,14 +34,16 @@ async fn test_list_products() -> Result<(), Box<dyn std::error::Error>> {
     let m = mock("GET", "/api/v1/products")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(LIST_BODY)
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.list_products(&None).await?;
+    let list = aha.list_products(&None, &Paging::default()).await?;
 
     m.assert();
+    assert_eq!(list.products[0].name, "Product");
+    assert_eq!(list.pagination.total_pages, 1);
     Ok(())
 }
 
@@ -43,13 +52,16 @@ async fn test_list_products_with_updated_since() -> Result<(), Box<dyn std::erro
     let m = mock("GET", "/api/v1/products?updated_since=2022-08-13T15:15:15Z")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(LIST_BODY)
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.list_products(&Some("2022-08-13T15:15:15Z".to_string()))
-        .await?;
+    aha.list_products(
+        &Some("2022-08-13T15:15:15Z".to_string()),
+        &Paging::default(),
+    )
+    .await?;
 
     m.assert();
     Ok(())
@@ -169,3 +181,62 @@ async fn test_update_product_without_name() -> Result<(), Box<dyn std::error::Er
     m.assert();
     Ok(())
 }
+
+#[async_std::test]
+async fn test_list_products_with_updated_since_and_per_page(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock(
+        "GET",
+        "/api/v1/products?updated_since=2022-08-13T15:15:15Z&per_page=200",
+    )
+    .match_header("Authorization", BEARER_TOKEN)
+    .with_status(200)
+    .with_body(LIST_BODY)
+    .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let paging = Paging {
+        per_page: Some(200),
+        ..Paging::default()
+    };
+    aha.list_products(&Some("2022-08-13T15:15:15Z".to_string()), &paging)
+        .await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_product_validation_message() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/products")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::PartialJson(json!({"product":{"prefix":"BAD"}})))
+        .with_status(422)
+        .with_body(r#"{"errors":{"message":"Prefix has already been taken"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let err = aha
+        .create_product("newname", "BAD", &None, "product_workspace")
+        .await
+        .unwrap_err();
+
+    m.assert();
+    assert_eq!(err.status(), Some(StatusCode::UnprocessableEntity));
+    assert_eq!(
+        err.to_string(),
+        "aha.io returned 422 Unprocessable Entity: Prefix has already been taken"
+    );
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_invalid_subdomain_is_an_error() -> Result<(), Box<dyn std::error::Error>> {
+    let aha = AhaRequest::new(TEST_TOKEN, "ac me");
+    let res = aha.list_products(&None, &Paging::default()).await;
+
+    assert!(matches!(res, Err(Error::Config(_))));
+    Ok(())
+}
@@ -1,24 +1,32 @@
-use blimey::aha::AhaRequest;
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{NewReleasePhase, ReleasePhaseUpdate};
+use blimey::Error;
 use mockito::{mock, Matcher};
 use serde_json::json;
 
 const TEST_TOKEN: &str = "test_token";
 const TEST_SUBDOMAIN: &str = "test_sub";
 const BEARER_TOKEN: &str = "Bearer test_token";
+const RELEASE_BODY: &str =
+    r#"{"release":{"id":"5001","reference_num":"PROD-R-1","name":"newname"}}"#;
 
 #[async_std::test]
 async fn test_get_release() -> Result<(), Box<dyn std::error::Error>> {
     let m = mock("GET", "/api/v1/releases/REL-1")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(
+            r#"{"release":{"id":"5001","reference_num":"PROD-R-1","name":"Release 1","release_date":"2021-12-01","workflow_status":{"id":"6001","name":"Shipped","complete":true}}}"#,
+        )
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.get_release("REL-1").await?;
+    let release = aha.get_release("REL-1").await?;
 
     m.assert();
+    assert_eq!(release.release_date.as_deref(), Some("2021-12-01"));
+    assert_eq!(release.workflow_status.unwrap().complete, Some(true));
     Ok(())
 }
 
@@ -27,14 +35,19 @@ async fn test_list_releases_for_product() -> Result<(), Box<dyn std::error::Erro
     let m = mock("GET", "/api/v1/products/PROD-1/releases")
         .match_header("Authorization", BEARER_TOKEN)
         .with_status(200)
-        .with_body(r#"{"message": "hello, world!"}"#)
+        .with_body(
+            r#"{"releases":[{"id":"5001","reference_num":"PROD-R-1","name":"Release 1"}],"pagination":{"total_records":1,"total_pages":1,"current_page":1}}"#,
+        )
         .create();
 
     let uri = &mockito::server_url();
     let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
-    aha.list_releases_for_product("PROD-1").await?;
+    let list = aha
+        .list_releases_for_product("PROD-1", &Paging::default())
+        .await?;
 
     m.assert();
+    assert_eq!(list.releases[0].reference_num.as_deref(), Some("PROD-R-1"));
     Ok(())
 }
 
@@ -112,3 +125,177 @@ async fn test_update_release_for_product_without_parent_id(
     m.assert();
     Ok(())
 }
+
+#[async_std::test]
+async fn test_delete_release() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("DELETE", "/api/v1/releases/REL-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(204)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    aha.delete_release("REL-1").await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_delete_release_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("DELETE", "/api/v1/releases/REL-404")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(404)
+        .with_body(r#"{"error":"Record not found"}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha.delete_release("REL-404").await;
+
+    m.assert();
+    assert!(res.is_err());
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_release_with_phases() -> Result<(), Box<dyn std::error::Error>> {
+    let release = mock("GET", "/api/v1/releases/PROD-R-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(RELEASE_BODY)
+        .create();
+    let phases = mock("GET", "/api/v1/releases/5001/release_phases?page=1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "release_phases":[
+                    {"id":"71","name":"Build","type":"phase","start_on":"2022-08-01","end_on":"2022-08-19"},
+                    {"id":"72","name":"Beta","type":"milestone","start_on":"2022-08-22","end_on":"2022-08-22"}
+                ],
+                "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha.get_release_with_phases("PROD-R-1").await?;
+
+    release.assert();
+    phases.assert();
+    assert_eq!(res.release_phases.len(), 2);
+    assert_eq!(
+        res.release_phases[1].phase_type.as_deref(),
+        Some("milestone")
+    );
+    let json = serde_json::to_value(&res)?;
+    assert_eq!(json["release_phases"][0]["type"], "phase");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_release_phase() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/releases/PROD-R-1/release_phases")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"release_phase":{
+            "name":"Beta",
+            "type":"milestone",
+            "start_on":"2022-08-22",
+            "end_on":"2022-08-22"
+        }})))
+        .with_status(200)
+        .with_body(r#"{"release_phase":{"id":"72","name":"Beta","type":"milestone"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let phase = aha
+        .create_release_phase(
+            "PROD-R-1",
+            &NewReleasePhase {
+                name: "Beta".into(),
+                phase_type: "milestone".into(),
+                start_on: "2022-08-22".into(),
+                end_on: Some("2022-08-22".into()),
+                description: None,
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(phase.id, "72");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_release_phase_dates_out_of_order() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/release_phases/71")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .update_release_phase(
+            "71",
+            &ReleasePhaseUpdate {
+                start_on: Some("2022-08-19".into()),
+                end_on: Some("2022-08-01".into()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_release_phase() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/release_phases/71")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(
+            json!({"release_phase":{"end_on":"2022-08-26"}}),
+        ))
+        .with_status(200)
+        .with_body(r#"{"release_phase":{"id":"71","name":"Build","end_on":"2022-08-26"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let phase = aha
+        .update_release_phase(
+            "71",
+            &ReleasePhaseUpdate {
+                end_on: Some("2022-08-26".into()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(phase.end_on.as_deref(), Some("2022-08-26"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_delete_release_phase() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("DELETE", "/api/v1/release_phases/71")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(204)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    aha.delete_release_phase("71").await?;
+
+    m.assert();
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{NewRequirement, RequirementUpdate};
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_get_requirement() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/requirements/FEAT-1-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            r#"{"requirement":{"id":"7001","reference_num":"FEAT-1-1","name":"Requirement","workflow_status":{"id":"2001","name":"Ready to develop"}}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let requirement = aha.get_requirement("FEAT-1-1").await?;
+
+    m.assert();
+    assert_eq!(requirement.reference_num.as_deref(), Some("FEAT-1-1"));
+    assert_eq!(
+        requirement.workflow_status.unwrap().name,
+        "Ready to develop"
+    );
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_delete_requirement() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("DELETE", "/api/v1/requirements/FEAT-1-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(204)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    aha.delete_requirement("FEAT-1-1").await?;
+
+    m.assert();
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_requirements_for_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/FEAT-1/requirements")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "requirements":[
+                    {"id":"7001","reference_num":"FEAT-1-1","name":"First","position":1},
+                    {"id":"7002","reference_num":"FEAT-1-2","name":"Second","position":2}
+                ],
+                "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_requirements_for_feature("FEAT-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.requirements.len(), 2);
+    assert_eq!(list.requirements[1].position, Some(2));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_requirement() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/FEAT-1/requirements")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "requirement": {"name": "Validate email", "assigned_to_user": "dev@example.com"}
+        })))
+        .with_status(200)
+        .with_body(
+            r#"{"requirement":{"id":"7003","reference_num":"FEAT-1-3","name":"Validate email"}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let requirement = NewRequirement {
+        name: "Validate email".to_string(),
+        assigned_to_user: Some("dev@example.com".to_string()),
+        ..NewRequirement::default()
+    };
+    let requirement = aha
+        .create_requirement("FEAT-1", &requirement)
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(requirement.id, "7003");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_requirement_status() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/requirements/FEAT-1-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "requirement": {"workflow_status": "Shipped"}
+        })))
+        .with_status(200)
+        .with_body(r#"{"requirement":{"id":"7001","name":"First","workflow_status":{"id":"2002","name":"Shipped"}}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let update = RequirementUpdate {
+        workflow_status: Some("Shipped".to_string()),
+        ..RequirementUpdate::default()
+    };
+    let requirement = aha.update_requirement("FEAT-1-1", &update).await?.unwrap();
+
+    m.assert();
+    assert_eq!(requirement.workflow_status.unwrap().name, "Shipped");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_reorder_requirements() -> Result<(), Box<dyn std::error::Error>> {
+    let m_first = mock("PUT", "/api/v1/requirements/FEAT-1-2")
+        .match_body(Matcher::Json(json!({"requirement": {"position": 1}})))
+        .with_status(200)
+        .with_body(r#"{"requirement":{"id":"7002","name":"Second","position":1}}"#)
+        .create();
+    let m_second = mock("PUT", "/api/v1/requirements/FEAT-1-1")
+        .match_body(Matcher::Json(json!({"requirement": {"position": 2}})))
+        .with_status(200)
+        .with_body(r#"{"requirement":{"id":"7001","name":"First","position":2}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let ids = vec!["FEAT-1-2".to_string(), "FEAT-1-1".to_string()];
+    let requirements = aha.reorder_requirements(&ids).await?;
+
+    m_first.assert();
+    m_second.assert();
+    assert_eq!(requirements[0].id, "7002");
+    assert_eq!(requirements[1].position, Some(2));
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use blimey::aha::AhaRequest;
+use blimey::retry::RetryPolicy;
+use blimey::Error;
+use http_types::other::RetryAfter;
+use http_types::StatusCode;
+use mockito::mock;
+use std::time::{Duration, Instant, SystemTime};
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_retry_after_rate_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let limited = mock("GET", "/api/v1/features/FEAT-429")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(429)
+        .with_header("Retry-After", "1")
+        .expect(1)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let started = Instant::now();
+    // mockito always answers with the newest matching mock, so the successful
+    // response is registered while the client waits out the Retry-After
+    let (feature, ok) = futures::join!(aha.get_feature("FEAT-429"), async {
+        async_std::task::sleep(Duration::from_millis(300)).await;
+        mock("GET", "/api/v1/features/FEAT-429")
+            .match_header("Authorization", BEARER_TOKEN)
+            .with_status(200)
+            .with_body(r#"{"feature":{"id":"1429","reference_num":"FEAT-429","name":"Limited"}}"#)
+            .expect(1)
+            .create()
+    });
+
+    limited.assert();
+    ok.assert();
+    assert_eq!(feature?.name, "Limited");
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_retry_gives_up_after_max_retries() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/FEAT-430")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(429)
+        .with_body(r#"{"error":"Rate limit exceeded"}"#)
+        .expect(3)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha =
+        AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri).with_retry_policy(RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        });
+    let res = aha.get_feature("FEAT-430").await;
+
+    m.assert();
+    match res {
+        Err(Error::Http { status, message }) => {
+            assert_eq!(status, StatusCode::TooManyRequests);
+            assert_eq!(message, "Rate limit exceeded");
+        }
+        other => panic!("expected an http error, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_retry_after_is_capped_by_max_delay() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/FEAT-431")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(429)
+        .with_header("Retry-After", "3600")
+        .expect(2)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha =
+        AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri).with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        });
+    let started = Instant::now();
+    let res = aha.get_feature("FEAT-431").await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Http { .. })));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_retry_after_http_date() -> Result<(), Box<dyn std::error::Error>> {
+    let at = SystemTime::now() + Duration::from_secs(2);
+    let limited = mock("GET", "/api/v1/features/FEAT-432")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(503)
+        .with_header("Retry-After", RetryAfter::new_at(at).value().as_str())
+        .expect(1)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha =
+        AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri).with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(30),
+        });
+    let started = Instant::now();
+    let (feature, ok) = futures::join!(aha.get_feature("FEAT-432"), async {
+        async_std::task::sleep(Duration::from_millis(300)).await;
+        mock("GET", "/api/v1/features/FEAT-432")
+            .with_status(200)
+            .with_body(r#"{"feature":{"id":"1432","name":"Unavailable"}}"#)
+            .expect(1)
+            .create()
+    });
+
+    limited.assert();
+    ok.assert();
+    assert_eq!(feature?.name, "Unavailable");
+    // HTTP dates have whole seconds, so at least one of the two is waited out
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    Ok(())
+}
+
+#[test]
+fn test_backoff_is_bounded() {
+    let policy = RetryPolicy {
+        max_retries: 10,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+    };
+    for retry in 0..10 {
+        let delay = policy.backoff(retry);
+        let cap = Duration::from_millis(100 * 2u64.pow(retry)).min(Duration::from_secs(1));
+        assert!(
+            delay >= cap / 2 && delay <= cap,
+            "{:?} for retry {}",
+            delay,
+            retry
+        );
+    }
+}
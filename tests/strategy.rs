@@ -0,0 +1,146 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{NewStrategy, StrategyUpdate};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+const INITIATIVE_BODY: &str = r#"{"initiative":{"id":"5001","reference_num":"PROD-S-1","name":"Self serve","progress":25.0}}"#;
+const GOAL_BODY: &str =
+    r#"{"goal":{"id":"6001","reference_num":"PROD-G-1","name":"Grow revenue","progress":60.0}}"#;
+
+#[async_std::test]
+async fn test_list_initiatives_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-1/initiatives")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "initiatives":[{"id":"5001","reference_num":"PROD-S-1","name":"Self serve"}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_initiatives_for_product("PROD-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(list.initiatives[0].name, "Self serve");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_initiative_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/products/PROD-1/initiatives")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"initiative": {"name": "Self serve"}})))
+        .with_status(200)
+        .with_body(INITIATIVE_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let initiative = NewStrategy {
+        name: "Self serve".to_string(),
+        ..NewStrategy::default()
+    };
+    let initiative = aha
+        .create_initiative_for_product("PROD-1", &initiative)
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(initiative.id, "5001");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_goal_progress() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/goals/PROD-G-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"goal": {"progress": 60.0}})))
+        .with_status(200)
+        .with_body(GOAL_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let update = StrategyUpdate {
+        progress: Some(60.0),
+        ..StrategyUpdate::default()
+    };
+    let goal = aha.update_goal("PROD-G-1", &update).await?.unwrap();
+
+    m.assert();
+    assert_eq!(goal.progress, Some(60.0));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_initiative_with_nothing_to_update() {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .update_initiative("PROD-S-1", &StrategyUpdate::default())
+        .await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+}
+
+#[async_std::test]
+async fn test_link_epic_to_initiative() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/epics/PROD-E-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"epic": {"initiative": "PROD-S-1"}})))
+        .with_status(200)
+        .with_body(
+            r#"{"epic":{"id":"7001","name":"Onboarding","initiative":{"id":"5001","name":"Self serve"}}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let epic = aha
+        .link_epic_to_initiative("PROD-E-1", "PROD-S-1")
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(epic.initiative.unwrap().id, "5001");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_link_feature_to_goal_keeps_existing_goals() -> Result<(), Box<dyn std::error::Error>>
+{
+    let m_get = mock("GET", "/api/v1/features/FEAT-1")
+        .with_status(200)
+        .with_body(
+            r#"{"feature":{"id":"1001","name":"Sign up","goals":[{"id":"6002","name":"Retention"}]}}"#,
+        )
+        .create();
+    let m_put = mock("PUT", "/api/v1/features/1001")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature": {"goals": "6002,6001"}})))
+        .with_status(200)
+        .with_body(
+            r#"{"feature":{"id":"1001","name":"Sign up","goals":[{"id":"6002","name":"Retention"},{"id":"6001","name":"Grow revenue"}]}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha.link_feature_to_goal("FEAT-1", "6001").await?.unwrap();
+
+    m_get.assert();
+    m_put.assert();
+    assert_eq!(feature.goals.len(), 2);
+    Ok(())
+}
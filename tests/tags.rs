@@ -0,0 +1,90 @@
+use blimey::aha::AhaRequest;
+use blimey::models::RecordKind;
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[async_std::test]
+async fn test_get_tags_of_idea() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/ideas/PROD-I-3")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(r#"{"idea":{"id":"8001","name":"Dark mode","tags":["ui","mobile"]}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let tags = aha.get_tags(RecordKind::Idea, "PROD-I-3").await?;
+
+    m.assert();
+    assert_eq!(tags, vec!["ui", "mobile"]);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_add_tags_to_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m_get = mock("GET", "/api/v1/features/PROD-12")
+        .with_status(200)
+        .with_body(r#"{"feature":{"id":"1001","name":"Sign up","tags":["growth"]}}"#)
+        .create();
+    let m_put = mock("PUT", "/api/v1/features/PROD-12")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature": {"tags": "growth,q3"}})))
+        .with_status(200)
+        .with_body(r#"{"feature":{"id":"1001","name":"Sign up","tags":["growth","q3"]}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let tags = aha
+        .add_tags(
+            RecordKind::Feature,
+            "PROD-12",
+            &["q3".to_string(), "growth".to_string()],
+        )
+        .await?;
+
+    m_get.assert();
+    m_put.assert();
+    assert_eq!(tags, vec!["growth", "q3"]);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_remove_tags_from_epic() -> Result<(), Box<dyn std::error::Error>> {
+    let m_get = mock("GET", "/api/v1/epics/PROD-E-4")
+        .with_status(200)
+        .with_body(r#"{"epic":{"id":"7001","name":"Onboarding","tags":["growth","q3"]}}"#)
+        .create();
+    let m_put = mock("PUT", "/api/v1/epics/PROD-E-4")
+        .match_body(Matcher::Json(json!({"epic": {"tags": "growth"}})))
+        .with_status(200)
+        .with_body(r#"{"epic":{"id":"7001","name":"Onboarding","tags":["growth"]}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let tags = aha
+        .remove_tags(RecordKind::Epic, "PROD-E-4", &["q3".to_string()])
+        .await?;
+
+    m_get.assert();
+    m_put.assert();
+    assert_eq!(tags, vec!["growth"]);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_set_tags_on_requirement() {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .set_tags(RecordKind::Requirement, "PROD-12-1", &["q3".to_string()])
+        .await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+}
@@ -0,0 +1,197 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{FeatureUpdate, NewTimeTrackingEvent, RecordKind, WorkAmount};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+#[test]
+fn test_parse_work_amount() {
+    let parse = |s: &str| s.parse::<WorkAmount>();
+    assert_eq!(parse("2h").unwrap(), WorkAmount::Minutes(120.0));
+    assert_eq!(parse("1h 30m").unwrap(), WorkAmount::Minutes(90.0));
+    assert_eq!(parse("1d").unwrap(), WorkAmount::Minutes(480.0));
+    assert_eq!(parse("1.5h").unwrap(), WorkAmount::Minutes(90.0));
+    assert_eq!(parse("3p").unwrap(), WorkAmount::Points(3.0));
+    assert_eq!(parse("0h").unwrap(), WorkAmount::Minutes(0.0));
+    for invalid in &["", "2", "h", "2w", "1h3p"] {
+        assert!(
+            matches!(parse(invalid), Err(Error::Validation(_))),
+            "{} should be rejected",
+            invalid
+        );
+    }
+}
+
+#[async_std::test]
+async fn test_list_time_tracking_events() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/requirements/PROD-1-1/time_tracking_events")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "time_tracking_events":[
+                    {"id":"61","work_done":120,"work_done_units":10,"work_date":"2022-08-15","user":{"id":"9","name":"Ada"}}
+                ],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let events = aha
+        .list_time_tracking_events(RecordKind::Requirement, "PROD-1-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(events.time_tracking_events[0].work_done, Some(120.0));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_log_time() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-123/time_tracking_events")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"time_tracking_event":{
+            "work_done":120.0,
+            "work_done_units":10,
+            "description":"pairing"
+        }})))
+        .with_status(200)
+        .with_body(r#"{"time_tracking_event":{"id":"62","work_done":120,"work_done_units":10}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let event = NewTimeTrackingEvent {
+        description: Some("pairing".into()),
+        ..NewTimeTrackingEvent::new("2h".parse()?)
+    };
+    let logged = aha
+        .log_time(RecordKind::Feature, "PROD-123", &event)
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(logged.id, "62");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_log_time_on_an_idea() -> Result<(), Box<dyn std::error::Error>> {
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let event = NewTimeTrackingEvent::new(WorkAmount::Minutes(30.0));
+    let res = aha.log_time(RecordKind::Idea, "PROD-I-1", &event).await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_feature_estimates() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/PROD-123")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature":{
+            "original_estimate_text":"3d",
+            "remaining_estimate_text":"1d 4h",
+            "work_done_text":"12h"
+        }})))
+        .with_status(200)
+        .with_body(r#"{"feature":{"id":"1123","name":"Feature","remaining_estimate":720}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .update_feature(
+            "PROD-123",
+            &FeatureUpdate {
+                original_estimate: Some("3d".into()),
+                remaining_estimate: Some("1d 4h".into()),
+                work_done: Some("12h".into()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(feature.remaining_estimate, Some(720.0));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_log_no_time() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-123/time_tracking_events")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let event = NewTimeTrackingEvent::new("0h".parse()?);
+    let res = aha.log_time(RecordKind::Feature, "PROD-123", &event).await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_feature_no_remaining_estimate() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/PROD-123")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(
+            json!({"feature":{"remaining_estimate_text":"0h"}}),
+        ))
+        .with_status(200)
+        .with_body(r#"{"feature":{"id":"1123","name":"Feature","remaining_estimate":0}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .update_feature(
+            "PROD-123",
+            &FeatureUpdate {
+                remaining_estimate: Some("0h".into()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(feature.remaining_estimate, Some(0.0));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_feature_invalid_estimate() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/PROD-123")
+        .with_status(200)
+        .expect(0)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .update_feature(
+            "PROD-123",
+            &FeatureUpdate {
+                work_done: Some("two hours".into()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    m.assert();
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
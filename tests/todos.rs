@@ -0,0 +1,104 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::models::{NewTodo, RecordKind};
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+const TODO_BODY: &str = r#"{"task":{"id":"4001","name":"Review copy","status":"pending","due_date":"2022-09-01","assigned_to_users":[{"id":"3001","name":"Jade","email":"jade@example.com"}]}}"#;
+
+#[async_std::test]
+async fn test_list_my_todos() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/me/tasks")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "tasks":[{"id":"4001","name":"Review copy","status":"pending"}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha.list_my_todos(&Paging::default()).await?;
+
+    m.assert();
+    assert_eq!(list.tasks[0].status.as_deref(), Some("pending"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_list_todos_for_feature() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/features/PROD-12/tasks")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            r#"{"tasks":[],"pagination":{"total_records":0,"total_pages":0,"current_page":1}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_todos(RecordKind::Feature, "PROD-12", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert!(list.tasks.is_empty());
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_create_todo() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("POST", "/api/v1/features/PROD-12/tasks")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({
+            "task": {
+                "name": "Review copy",
+                "due_date": "2022-09-01",
+                "assigned_to_users": [{"email": "jade@example.com"}, {"id": "3002"}]
+            }
+        })))
+        .with_status(200)
+        .with_body(TODO_BODY)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let todo = NewTodo {
+        name: "Review copy".to_string(),
+        due_date: Some("2022-09-01".to_string()),
+        assigned_to_users: vec!["jade@example.com".to_string(), "3002".to_string()],
+        ..NewTodo::default()
+    };
+    let todo = aha
+        .create_todo(RecordKind::Feature, "PROD-12", &todo)
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(todo.assigned_to_users[0].name.as_deref(), Some("Jade"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_complete_todo() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/tasks/4001")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"task": {"status": "completed"}})))
+        .with_status(200)
+        .with_body(r#"{"task":{"id":"4001","name":"Review copy","status":"completed"}}"#)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let todo = aha.complete_todo("4001").await?.unwrap();
+
+    m.assert();
+    assert_eq!(todo.status.as_deref(), Some("completed"));
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use blimey::aha::{AhaRequest, Paging};
+use blimey::Error;
+use mockito::mock;
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+fn users_body() -> String {
+    json!({
+        "users":[
+            {"id":"3001","name":"Jade","email":"jade@example.com"},
+            {"id":"3002","name":"Sam","email":"Sam@Example.com"}
+        ],
+        "pagination":{"total_records":2,"total_pages":1,"current_page":1}
+    })
+    .to_string()
+}
+
+#[async_std::test]
+async fn test_list_users_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/products/PROD-1/users")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({
+                "users":[{"id":"3001","name":"Jade","email":"jade@example.com","role":20,"role_description":"Product owner"}],
+                "pagination":{"total_records":1,"total_pages":1,"current_page":1}
+            })
+            .to_string(),
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let list = aha
+        .list_users_for_product("PROD-1", &Paging::default())
+        .await?;
+
+    m.assert();
+    assert_eq!(
+        list.users[0].role_description.as_deref(),
+        Some("Product owner")
+    );
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_get_user_by_email() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/users?page=1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(users_body())
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let user = aha.get_user("sam@example.com").await?;
+
+    m.assert();
+    assert_eq!(user.id, "3002");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_resolve_user_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("GET", "/api/v1/users?page=1")
+        .with_status(200)
+        .with_body(users_body())
+        .expect(1)
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let ids = aha
+        .resolve_user_ids(&[
+            "jade@example.com".to_string(),
+            "3005".to_string(),
+            "sam@example.com".to_string(),
+        ])
+        .await?;
+
+    m.assert();
+    assert_eq!(ids, vec!["3001", "3005", "3002"]);
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_resolve_unknown_email() -> Result<(), Box<dyn std::error::Error>> {
+    let _m = mock("GET", "/api/v1/users?page=1")
+        .with_status(200)
+        .with_body(users_body())
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha
+        .resolve_user_ids(&["nobody@example.com".to_string()])
+        .await;
+
+    assert!(matches!(res, Err(Error::Validation(_))));
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use blimey::aha::AhaRequest;
+use blimey::models::{Feature, FeatureUpdate, WorkflowStatus};
+use blimey::Error;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+const TEST_TOKEN: &str = "test_token";
+const TEST_SUBDOMAIN: &str = "test_sub";
+const BEARER_TOKEN: &str = "Bearer test_token";
+
+fn workflows_mock() -> mockito::Mock {
+    mock("GET", "/api/v1/products/PROD/workflows")
+        .match_header("Authorization", BEARER_TOKEN)
+        .with_status(200)
+        .with_body(
+            json!({"workflows":[
+                {"id":"1","name":"Bug workflow","workflow_statuses":[
+                    {"id":"11","name":"Reported","position":1},
+                    {"id":"12","name":"Fixed","position":2,"complete":true}
+                ]},
+                {"id":"2","name":"Product workflow","workflow_statuses":[
+                    {"id":"21","name":"Under consideration","position":1},
+                    {"id":"22","name":"In development","position":2},
+                    {"id":"23","name":"Shipped","position":3,"complete":true}
+                ]}
+            ]})
+            .to_string(),
+        )
+        .create()
+}
+
+fn feature_in(status_id: &str) -> Feature {
+    Feature {
+        id: "1001".into(),
+        reference_num: Some("PROD-1".into()),
+        product_id: Some("PROD".into()),
+        workflow_status: Some(WorkflowStatus {
+            id: status_id.into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[async_std::test]
+async fn test_list_workflows_for_product() -> Result<(), Box<dyn std::error::Error>> {
+    let m = workflows_mock();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let workflows = aha.list_workflows_for_product("PROD").await?;
+
+    m.assert();
+    assert_eq!(workflows.len(), 2);
+    assert_eq!(workflows[1].workflow_statuses[2].name, "Shipped");
+    assert_eq!(workflows[1].workflow_statuses[2].complete, Some(true));
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_resolve_feature_status() -> Result<(), Box<dyn std::error::Error>> {
+    let m = workflows_mock();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let status = aha
+        .resolve_feature_status(&feature_in("21"), "in development")
+        .await?;
+
+    m.assert();
+    assert_eq!(status.id, "22");
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_resolve_feature_status_from_other_workflow() -> Result<(), Box<dyn std::error::Error>>
+{
+    let _m = workflows_mock();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let res = aha.resolve_feature_status(&feature_in("21"), "Fixed").await;
+
+    match res {
+        Err(Error::Validation(msg)) => {
+            assert!(msg.contains("Product workflow"));
+            assert!(msg.contains("Under consideration, In development, Shipped"));
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[async_std::test]
+async fn test_update_feature_status() -> Result<(), Box<dyn std::error::Error>> {
+    let m = mock("PUT", "/api/v1/features/PROD-1")
+        .match_header("Authorization", BEARER_TOKEN)
+        .match_body(Matcher::Json(json!({"feature":{"workflow_status":"22"}})))
+        .with_status(200)
+        .with_body(
+            r#"{"feature":{"id":"1001","name":"One","workflow_status":{"id":"22","name":"In development"}}}"#,
+        )
+        .create();
+
+    let uri = &mockito::server_url();
+    let aha = AhaRequest::with_url(TEST_TOKEN, TEST_SUBDOMAIN, uri);
+    let feature = aha
+        .update_feature(
+            "PROD-1",
+            &FeatureUpdate {
+                workflow_status: Some("22".into()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .unwrap();
+
+    m.assert();
+    assert_eq!(feature.workflow_status.unwrap().name, "In development");
+    Ok(())
+}